@@ -24,4 +24,166 @@ pub enum MyError {
     InvalidMerkleTreeAddress,
     #[msg("Invalid collection address")]
     InvalidCollectionAddress,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Lottery is paused")]
+    LotteryPaused,
+    #[msg("This lottery requires the authorizer wallet co-signature")]
+    CosignRequired,
+    #[msg("Voucher signature instruction is missing")]
+    MissingVoucherSignature,
+    #[msg("Voucher signature is invalid")]
+    InvalidVoucherSignature,
+    #[msg("Voucher accounts are missing")]
+    MissingVoucherAccounts,
+    #[msg("Voucher was issued for a different buyer or lottery")]
+    InvalidVoucher,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Payment amount does not match the voucher price")]
+    VoucherPriceMismatch,
+    #[msg("Voucher has already been fully redeemed")]
+    VoucherExhausted,
+    #[msg("Buyer is not on the lottery allowlist")]
+    NotAllowlisted,
+    #[msg("Buyer record account is missing")]
+    MissingBuyerRecord,
+    #[msg("Buyer has reached their allowlist ticket cap")]
+    AllowlistCapReached,
+    #[msg("Symbol is too long")]
+    SymbolTooLong,
+    #[msg("Base URI is too long")]
+    BaseUriTooLong,
+    #[msg("Invalid pick configuration")]
+    InvalidPickConfig,
+    #[msg("Invalid royalty configuration")]
+    InvalidRoyaltyConfig,
+    #[msg("Picks must be distinct, sorted and within the lottery range")]
+    InvalidPicks,
+    #[msg("Ticket picks account is missing")]
+    MissingTicketPicks,
+    #[msg("Lottery is not open")]
+    LotteryNotOpen,
+    #[msg("Lottery draw has not been committed")]
+    DrawNotCommitted,
+    #[msg("Lottery has not been drawn")]
+    LotteryNotDrawn,
+    #[msg("Draw secret does not match the commitment")]
+    InvalidDrawSecret,
+    #[msg("Lottery has no tickets")]
+    NoTickets,
+    #[msg("Ticket did not win a prize")]
+    NoPrize,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Prize pool is exhausted")]
+    PrizePoolExhausted,
+    #[msg("Lottery has reached its maximum number of trees")]
+    TooManyTrees,
+    #[msg("All lottery trees are full")]
+    NoTreeAvailable,
+    #[msg("Invalid canopy depth")]
+    InvalidCanopyDepth,
+    #[msg("Proof length does not match the tree depth minus its canopy")]
+    InvalidProofLength,
+    #[msg("Lottery is not settled or cancelled")]
+    LotteryNotFinished,
+    #[msg("Lottery vault still holds funds")]
+    VaultNotEmpty,
+    #[msg("Lottery can no longer be cancelled")]
+    LotteryNotCancellable,
+    #[msg("Rent must be returned to the original payer")]
+    InvalidRentPayer,
+    #[msg("Transfer lock window must end after it starts")]
+    InvalidTransferLock,
+    #[msg("Tickets are locked around the draw")]
+    TicketsLocked,
+    #[msg("Invalid protocol fee")]
+    InvalidProtocolFee,
+    #[msg("Listing price must be greater than zero")]
+    InvalidListingPrice,
+    #[msg("Creator accounts don't match the lottery royalty settings")]
+    InvalidCreatorAccounts,
+    #[msg("Invalid referral share")]
+    InvalidReferralBps,
+    #[msg("Buyers can't refer themselves")]
+    SelfReferral,
+    #[msg("The referrer balance account is required with a referrer")]
+    MissingReferrerBalance,
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+    #[msg("Promo code is not part of the campaign")]
+    InvalidPromoCode,
+    #[msg("Promo campaign budget is exhausted")]
+    PromoBudgetExhausted,
+    #[msg("Invalid discount collections")]
+    InvalidDiscountCollections,
+    #[msg("Invalid proof of holding a partner NFT")]
+    InvalidHolderProof,
+    #[msg("NFT is not part of an eligible verified collection")]
+    CollectionNotEligible,
+    #[msg("Ticket price is above the buyer's maximum price")]
+    PriceAboveMax,
+    #[msg("Invalid pricing model")]
+    InvalidPricingModel,
+    #[msg("No-loss mode must be enabled on an open lottery before any ticket is sold, without referral rewards")]
+    NoLossUnavailable,
+    #[msg("The yield vault is required in no-loss mode")]
+    MissingYieldVault,
+    #[msg("Invalid yield program")]
+    InvalidYieldProgram,
+    #[msg("Invalid yield source position")]
+    InvalidYieldPosition,
+    #[msg("Not enough principal in the vault, withdraw it from the yield source first")]
+    InsufficientLiquidity,
+    #[msg("No yield to harvest")]
+    NoYield,
+    #[msg("Lottery is already settled or cancelled")]
+    LotteryFinished,
+    #[msg("Referral rewards aren't available in no-loss mode")]
+    NoLossReferral,
+    #[msg("The draw slot has not passed yet")]
+    DrawSlotNotReached,
+    #[msg("The draw slot hash is not available in the SlotHashes sysvar")]
+    SlotHashUnavailable,
+    #[msg("The draw can no longer be revealed")]
+    RevealExpired,
+    #[msg("The draw can still be revealed")]
+    RevealNotExpired,
+    #[msg("Ticket record doesn't match the drawn ticket")]
+    InvalidTicketRecord,
+    #[msg("No live ticket drawn, pass the ticket records of more draw attempts")]
+    NoLiveTicketDrawn,
+    #[msg("The win registration window is closed")]
+    WinRegistrationClosed,
+    #[msg("Ticket is already registered as a winner")]
+    WinAlreadyRegistered,
+    #[msg("Ticket was not registered as a winner")]
+    WinNotRegistered,
+    #[msg("Prizes can't be claimed before the win registration window closes")]
+    ClaimsNotOpen,
+    #[msg("Tree config doesn't belong to the expected tree")]
+    InvalidTreeConfig,
+    #[msg("Can't roll over past a tree that is not full")]
+    TreeNotFull,
+    #[msg("Minting into the tree must be signed by its delegate, and only by it")]
+    InvalidTreeDelegate,
+    #[msg("The claim period is not over yet")]
+    ClaimPeriodNotOver,
+    #[msg("Only the recorded holder can use a locked ticket during the transfer lock window")]
+    TicketLockBypassed,
+    #[msg("Lottery account is not closed yet")]
+    LotteryNotClosed,
+    #[msg("No-loss tickets must deposit principal")]
+    ZeroDeposit,
+    #[msg("Ticket principal is still owed to holders")]
+    PrincipalOutstanding,
+    #[msg("Treasury doesn't match the protocol treasury")]
+    InvalidTreasury,
+    #[msg("The protocol treasury is not set, configure it with set_protocol_fee")]
+    TreasuryNotSet,
+    #[msg("Holder discounts only apply to lotteries with a pricing model")]
+    DiscountNotApplicable,
+    #[msg("Royalty and protocol fee together exceed the sale price")]
+    ResaleFeesTooHigh,
 }
\ No newline at end of file
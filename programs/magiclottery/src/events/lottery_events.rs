@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::{DiscountCollection, PricingModel, RefundPolicy};
+
+/// Emitted when the central authority is initialized
+#[event]
+pub struct CentralAuthorityInitialized {
+    pub authorizer_wallet: Pubkey,
+}
+
+/// Emitted when a lottery collection and its state account are created
+#[event]
+pub struct LotteryCreated {
+    pub lottery: Pubkey,
+    pub collection_mint: Pubkey,
+    pub requires_cosign: bool,
+}
+
+/// Emitted when the metadata of a lottery collection NFT is updated
+#[event]
+pub struct LotteryMetadataUpdated {
+    pub lottery: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// Emitted when a Merkle tree for lottery tickets is created
+#[event]
+pub struct TreeCreated {
+    pub lottery: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub canopy_depth: u32,
+}
+
+/// Emitted when minting into a lottery tree is delegated
+#[event]
+pub struct TreeDelegateUpdated {
+    pub lottery: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub tree_delegate: Pubkey,
+}
+
+/// Emitted when a ticket cNFT is minted to a buyer
+#[event]
+pub struct TicketPurchased {
+    pub lottery: Pubkey,
+    pub payer: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub serial: u64,
+    pub leaf_index: u64,
+    pub asset_id: Pubkey,
+    pub price: u64,
+}
+
+/// Emitted when a ticket cNFT is burned
+#[event]
+pub struct TicketDissolved {
+    pub lottery: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub serial: u64,
+    pub leaf_index: u32,
+    pub refund_receiver: Pubkey,
+    pub refund: u64,
+}
+
+/// Emitted when the randomness commitment of a lottery draw is recorded
+#[event]
+pub struct DrawCommitted {
+    pub lottery: Pubkey,
+    pub commitment: [u8; 32],
+    pub draw_slot: u64,
+}
+
+/// Emitted when a lottery draw selects its winning ticket, or its winning numbers in pick mode
+#[event]
+pub struct WinnerSelected {
+    pub lottery: Pubkey,
+    pub winning_ticket: u64,
+    pub winning_numbers: Vec<u8>,
+    pub prize_pool: u64,
+}
+
+/// Emitted when a winning pick mode ticket is registered for its prize tier
+#[event]
+pub struct WinRegistered {
+    pub lottery: Pubkey,
+    pub serial: u64,
+    pub matches: u8,
+}
+
+/// Emitted when a prize is paid out to a ticket holder
+#[event]
+pub struct PrizeClaimed {
+    pub lottery: Pubkey,
+    pub winner: Pubkey,
+    pub serial: u64,
+    pub matches: u8,
+    pub amount: u64,
+    pub principal_refund: u64,
+}
+
+/// Emitted when a drawn lottery is settled and its remaining funds swept
+#[event]
+pub struct LotterySettled {
+    pub lottery: Pubkey,
+    pub treasury: Pubkey,
+    pub swept: u64,
+}
+
+/// Emitted when a lottery is cancelled before its draw
+#[event]
+pub struct LotteryCancelled {
+    pub lottery: Pubkey,
+}
+
+/// Emitted when a finished lottery's accounts are closed
+#[event]
+pub struct LotteryClosed {
+    pub lottery: Pubkey,
+    pub rent_payer: Pubkey,
+}
+
+/// Emitted when a buyer closes their ticket counter
+#[event]
+pub struct BuyerRecordClosed {
+    pub lottery: Pubkey,
+    pub buyer: Pubkey,
+}
+
+/// Emitted when the whole program is paused or resumed
+#[event]
+pub struct ProgramPauseUpdated {
+    pub paused: bool,
+}
+
+/// Emitted when a single lottery is paused or resumed
+#[event]
+pub struct LotteryPauseUpdated {
+    pub lottery: Pubkey,
+    pub paused: bool,
+}
+
+/// Emitted when the ticket transfer lock window of a lottery is set
+#[event]
+pub struct TransferLockUpdated {
+    pub lottery: Pubkey,
+    pub starts_at: i64,
+    pub ends_at: i64,
+}
+
+/// Emitted when a ticket is released from the transfer lock
+#[event]
+pub struct TicketUnlocked {
+    pub lottery: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub serial: u64,
+}
+
+/// Emitted when a ticket is transferred through `transfer_ticket`
+#[event]
+pub struct TicketTransferred {
+    pub lottery: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub serial: u64,
+}
+
+/// Emitted when the protocol fee on secondary sales is updated
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub protocol_fee_bps: u16,
+    pub protocol_treasury: Pubkey,
+}
+
+/// Emitted when a ticket is listed for sale
+#[event]
+pub struct TicketListed {
+    pub lottery: Pubkey,
+    pub seller: Pubkey,
+    pub serial: u64,
+    pub price: u64,
+}
+
+/// Emitted when a listed ticket is bought
+#[event]
+pub struct TicketResold {
+    pub lottery: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub serial: u64,
+    pub price: u64,
+    pub royalty: u64,
+    pub protocol_fee: u64,
+}
+
+/// Emitted when a ticket listing is cancelled
+#[event]
+pub struct ListingCancelled {
+    pub lottery: Pubkey,
+    pub seller: Pubkey,
+    pub serial: u64,
+}
+
+/// Emitted when the refund policy of a lottery is updated
+#[event]
+pub struct RefundPolicyUpdated {
+    pub lottery: Pubkey,
+    pub refund_policy: RefundPolicy,
+}
+
+/// Emitted when a ticket record is closed
+#[event]
+pub struct TicketRecordClosed {
+    pub lottery: Pubkey,
+    pub payer: Pubkey,
+    pub serial: u64,
+}
+
+/// Emitted when a voucher redemption counter is closed
+#[event]
+pub struct VoucherRecordClosed {
+    pub lottery: Pubkey,
+    pub buyer: Pubkey,
+    pub nonce: u64,
+}
+
+/// Emitted when a promo code redemption is closed
+#[event]
+pub struct PromoRedemptionClosed {
+    pub lottery: Pubkey,
+    pub campaign: Pubkey,
+    pub redeemer: Pubkey,
+}
+
+/// Emitted when the referral share of a lottery is updated
+#[event]
+pub struct ReferralBpsUpdated {
+    pub lottery: Pubkey,
+    pub referral_bps: u16,
+}
+
+/// Emitted when a ticket purchase credits a referrer
+#[event]
+pub struct ReferralCredited {
+    pub lottery: Pubkey,
+    pub referrer: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a referrer withdraws their rewards
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub lottery: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a promo campaign is created
+#[event]
+pub struct PromoCampaignCreated {
+    pub lottery: Pubkey,
+    pub campaign: Pubkey,
+    pub campaign_id: u64,
+    pub budget: u32,
+}
+
+/// Emitted when a promo code is redeemed for a ticket
+#[event]
+pub struct PromoRedeemed {
+    pub lottery: Pubkey,
+    pub campaign: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub serial: u64,
+    pub asset_id: Pubkey,
+}
+
+/// Emitted when the pricing model of a lottery is set or cleared
+#[event]
+pub struct PricingUpdated {
+    pub lottery: Pubkey,
+    pub pricing: Option<PricingModel>,
+}
+
+/// Emitted when the discounted partner collections of a lottery are updated
+#[event]
+pub struct DiscountCollectionsUpdated {
+    pub lottery: Pubkey,
+    pub discount_collections: Vec<DiscountCollection>,
+}
+
+/// Emitted when no-loss mode is enabled on a lottery
+#[event]
+pub struct NoLossEnabled {
+    pub lottery: Pubkey,
+    pub yield_program: Pubkey,
+    pub position: Pubkey,
+}
+
+/// Emitted when principal is deposited into the yield source
+#[event]
+pub struct YieldDeposited {
+    pub lottery: Pubkey,
+    pub amount: u64,
+    pub deposited: u64,
+}
+
+/// Emitted when principal is withdrawn from the yield source back to the lottery vault
+#[event]
+pub struct YieldWithdrawn {
+    pub lottery: Pubkey,
+    pub amount: u64,
+    pub deposited: u64,
+}
+
+/// Emitted when accrued yield is withdrawn into the lottery vault
+#[event]
+pub struct YieldHarvested {
+    pub lottery: Pubkey,
+    pub amount: u64,
+    pub harvested_yield: u64,
+}
+
+/// Emitted when the allowlist root of a lottery is set or cleared
+#[event]
+pub struct AllowlistUpdated {
+    pub lottery: Pubkey,
+    pub allowlist_root: Option<[u8; 32]>,
+}
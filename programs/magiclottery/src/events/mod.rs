@@ -0,0 +1 @@
+pub mod lottery_events;
\ No newline at end of file
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+#[account]
+pub struct BuyerRecord {
+    pub lottery: Pubkey,
+    pub buyer: Pubkey,
+    pub tickets_bought: u32,
+}
+
+impl BuyerRecord {
+    pub const MAX_SIZE: usize = 32 + 32 + 4;
+}
@@ -3,8 +3,13 @@ use anchor_lang::prelude::*;
 pub struct CentralStateData {
     pub is_initialized: bool,
     pub authorizer_wallet: Pubkey,
+    pub paused: bool,
+    /// Fee taken on secondary ticket sales, in basis points
+    pub protocol_fee_bps: u16,
+    pub protocol_treasury: Pubkey,
 }
 
 impl CentralStateData {
+    // 1 + 32 + 1 + 2 + 32 bytes used, the rest is spare
     pub const MAX_SIZE: usize = 32 * 3;
 }
\ No newline at end of file
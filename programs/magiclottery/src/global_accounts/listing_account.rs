@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+#[account]
+pub struct TicketListing {
+    pub lottery: Pubkey,
+    pub seller: Pubkey,
+    pub serial: u64,
+    pub merkle_tree: Pubkey,
+    pub price: u64,
+}
+
+impl TicketListing {
+    pub const MAX_SIZE: usize = 32 + 32 + 8 + 32 + 8;
+}
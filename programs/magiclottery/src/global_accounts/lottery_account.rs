@@ -0,0 +1,461 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::types::{Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard};
+use mpl_token_metadata::{MAX_CREATOR_LIMIT, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+use crate::REVEAL_WINDOW_SLOTS;
+
+/// Longest base URI that still fits `/<u64 serial>.json` within the metadata URI limit
+pub const MAX_BASE_URI_LENGTH: usize = MAX_URI_LENGTH - 26;
+/// Maximum number of picks a player can choose in pick mode
+pub const MAX_PICKS: usize = 10;
+/// Maximum number of Merkle trees a lottery can mint tickets into
+pub const MAX_TREES: usize = 10;
+/// Maximum number of partner collections whose holders get a discount
+pub const MAX_DISCOUNT_COLLECTIONS: usize = 5;
+/// Maximum number of tiers of a tiered pricing model
+pub const MAX_PRICE_TIERS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LotteryStatus {
+    /// Tickets can be bought and dissolved
+    Open,
+    /// Sales are closed and the draw randomness is committed
+    DrawCommitted,
+    /// The draw result is known and prizes can be claimed
+    Drawn,
+    /// Prizes are paid out and the remaining funds swept; the lottery can be closed
+    Settled,
+    /// The lottery was called off before the draw; tickets can still be dissolved
+    Cancelled,
+}
+
+/// Who receives the price of a dissolved ticket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RefundPolicy {
+    /// The wallet holding the ticket when it is dissolved
+    Holder,
+    /// The wallet that paid for the ticket, e.g. the giver of a gifted ticket
+    Payer,
+}
+
+/// Pick-6 style configuration: players choose `pick_count` distinct numbers in `1..=pick_max`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PickConfig {
+    pub pick_count: u8,
+    pub pick_max: u8,
+    /// Share of the prize pool split among the tickets of each tier, in basis points, indexed by match count
+    pub tier_payout_bps: Vec<u16>,
+}
+
+/// A creator receiving a share of the royalties paid on ticket resales
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RoyaltyShare {
+    pub address: Pubkey,
+    /// Percentage of the royalties, the shares of all creators add up to 100
+    pub share: u8,
+}
+
+impl RoyaltyShare {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/// Royalty settings applied to every ticket of a lottery
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RoyaltyConfig {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<RoyaltyShare>,
+}
+
+impl RoyaltyConfig {
+    /// Checks the fee is at most 100% and paid out to at most five creators whose shares add up to 100
+    pub fn is_valid(&self) -> bool {
+        let total_share: u32 = self.creators.iter().map(|creator| creator.share as u32).sum();
+        self.seller_fee_basis_points <= 10_000
+            && self.creators.len() <= MAX_CREATOR_LIMIT
+            && if self.creators.is_empty() {
+                self.seller_fee_basis_points == 0
+            } else {
+                total_share == 100
+            }
+    }
+}
+
+/// A price applying while fewer than `sold_up_to` tickets have been sold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceTier {
+    pub sold_up_to: u64,
+    pub price: u64,
+}
+
+impl PriceTier {
+    pub const SIZE: usize = 8 + 8;
+}
+
+/// How the ticket price is computed from the number of tickets sold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum PricingModel {
+    /// Every ticket costs `price`
+    Fixed { price: u64 },
+    /// The price starts at `base_price` and rises by `step_increment` every `step_size` tickets
+    LinearStep {
+        base_price: u64,
+        step_size: u64,
+        step_increment: u64,
+    },
+    /// The price of the first tier not yet sold out, the last tier applying beyond its bound
+    Tiered { tiers: Vec<PriceTier> },
+}
+
+impl PricingModel {
+    // Variant tag plus the largest variant, `Tiered`
+    pub const SIZE: usize = 1 + (4 + PriceTier::SIZE * MAX_PRICE_TIERS);
+
+    /// Checks linear steps are non-empty and tiers are non-empty, bounded and strictly increasing
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PricingModel::Fixed { .. } => true,
+            PricingModel::LinearStep { step_size, .. } => *step_size > 0,
+            PricingModel::Tiered { tiers } => {
+                !tiers.is_empty()
+                    && tiers.len() <= MAX_PRICE_TIERS
+                    && tiers.windows(2).all(|pair| pair[0].sold_up_to < pair[1].sold_up_to)
+            }
+        }
+    }
+
+    /// Price of the next ticket once `sold` tickets have been sold
+    pub fn price(&self, sold: u64) -> Option<u64> {
+        match self {
+            PricingModel::Fixed { price } => Some(*price),
+            PricingModel::LinearStep { base_price, step_size, step_increment } => {
+                (sold / step_size)
+                    .checked_mul(*step_increment)
+                    .and_then(|increase| base_price.checked_add(increase))
+            }
+            PricingModel::Tiered { tiers } => tiers.iter()
+                .find(|tier| sold < tier.sold_up_to)
+                .or(tiers.last())
+                .map(|tier| tier.price),
+        }
+    }
+}
+
+/// A verified collection whose holders get a discount on tickets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DiscountCollection {
+    pub collection: Pubkey,
+    pub discount_bps: u16,
+}
+
+impl DiscountCollection {
+    pub const SIZE: usize = 32 + 2;
+}
+
+/// A Merkle tree registered to a lottery for minting its tickets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TreeRegistration {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub canopy_depth: u32,
+    /// The minter set with `set_tree_delegate`, the only signer the program mints with when set
+    pub tree_delegate: Option<Pubkey>,
+}
+
+impl TreeRegistration {
+    pub const SIZE: usize = 32 + 4 + 4 + 4 + (1 + 32);
+
+    /// Number of leaves the tree can hold
+    pub fn capacity(&self) -> u64 {
+        1u64 << self.max_depth
+    }
+
+    /// Number of proof nodes a leaf operation must pass, the rest being cached in the canopy
+    pub fn proof_length(&self) -> usize {
+        (self.max_depth - self.canopy_depth) as usize
+    }
+}
+
+#[account]
+pub struct LotteryStateData {
+    pub collection_mint: Pubkey,
+    pub rent_payer: Pubkey,
+    pub paused: bool,
+    pub requires_cosign: bool,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub symbol: String,
+    pub base_uri: String,
+    pub tickets_minted: u64,
+    pub status: LotteryStatus,
+    pub pick_count: u8,
+    pub pick_max: u8,
+    pub tier_payout_bps: Vec<u16>,
+    pub draw_commitment: [u8; 32],
+    pub winning_ticket: u64,
+    pub winning_numbers: Vec<u8>,
+    pub prize_pool: u64,
+    pub trees: Vec<TreeRegistration>,
+    pub current_tree: u8,
+    /// Number of tickets minted and not yet burned, mirroring the sized collection counter
+    pub collection_size: u64,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<RoyaltyShare>,
+    pub transfer_lock_starts_at: i64,
+    pub transfer_lock_ends_at: i64,
+    pub refund_policy: RefundPolicy,
+    /// Share of each ticket price credited to the referrer, in basis points
+    pub referral_bps: u16,
+    /// How tickets are priced, `None` lets buyers choose their deposit
+    pub pricing: Option<PricingModel>,
+    pub discount_collections: Vec<DiscountCollection>,
+    /// Whether ticket payments are refundable principal and prizes are funded by yield
+    pub no_loss: bool,
+    /// Slot whose hash seeds the draw, set a few slots after the commitment
+    pub draw_slot: u64,
+    /// Number of registered winning tickets, indexed by match count
+    pub tier_winners: Vec<u32>,
+    /// When the win registration window closes and prizes can be claimed
+    pub claims_open_at: i64,
+    /// When the claim period ends and the lottery can be settled
+    pub claim_deadline: i64,
+}
+
+impl LotteryStateData {
+    pub const MAX_SIZE: usize = 32 + 32 + 1 + 1 + (1 + 32)
+        + (4 + MAX_SYMBOL_LENGTH)
+        + (4 + MAX_BASE_URI_LENGTH)
+        + 8
+        + 1 + 1 + 1
+        + (4 + 2 * (MAX_PICKS + 1))
+        + 32 + 8
+        + (4 + MAX_PICKS)
+        + 8
+        + (4 + TreeRegistration::SIZE * MAX_TREES)
+        + 1
+        + 8
+        + 2 + (4 + RoyaltyShare::SIZE * MAX_CREATOR_LIMIT)
+        + 8 + 8
+        + 1
+        + 2
+        + (1 + PricingModel::SIZE)
+        + (4 + DiscountCollection::SIZE * MAX_DISCOUNT_COLLECTIONS)
+        + 1
+        + 8
+        + (4 + 4 * (MAX_PICKS + 1))
+        + 8
+        + 8;
+
+    /// Name of the ticket with the given serial, e.g. `MAGIC #42`
+    pub fn ticket_name(&self, serial: u64) -> String {
+        format!("{} #{}", self.symbol, serial)
+    }
+
+    /// Metadata URI of the ticket with the given serial, e.g. `<base_uri>/42.json`
+    pub fn ticket_uri(&self, serial: u64) -> String {
+        format!("{}/{}.json", self.base_uri.trim_end_matches('/'), serial)
+    }
+
+    /// Compressed NFT metadata of the ticket with the given serial
+    ///
+    /// Claims rebuild it to recompute the leaf data hash, so it must stay identical
+    /// to what `buy_ticket` minted. Royalties come from the lottery settings; the
+    /// creators are unverified since they don't sign the mint.
+    pub fn ticket_metadata(&self, serial: u64) -> MetadataArgs {
+        MetadataArgs {
+            name: self.ticket_name(serial),
+            symbol: self.symbol.clone(),
+            uri: self.ticket_uri(serial),
+            creators: self.creators.iter()
+                .map(|creator| Creator {
+                    address: creator.address,
+                    verified: false,
+                    share: creator.share,
+                })
+                .collect(),
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: Some(0),
+            uses: None,
+            collection: Some(Collection {
+                verified: true,
+                key: self.collection_mint,
+            }),
+            token_program_version: TokenProgramVersion::Original,
+            token_standard: Some(TokenStandard::NonFungible),
+        }
+    }
+
+    /// Whether `merkle_tree` is one of the lottery's registered trees
+    pub fn has_tree(&self, merkle_tree: &Pubkey) -> bool {
+        self.find_tree(merkle_tree).is_some()
+    }
+
+    /// Registration of `merkle_tree`, if it is one of the lottery's trees
+    pub fn find_tree(&self, merkle_tree: &Pubkey) -> Option<&TreeRegistration> {
+        self.trees.iter().find(|tree| tree.merkle_tree == *merkle_tree)
+    }
+
+    /// Whether the lottery is over and its accounts can be closed
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, LotteryStatus::Settled | LotteryStatus::Cancelled)
+    }
+
+    /// Whether tickets can be dissolved: before the draw or once cancelled, and after
+    /// the draw too in no-loss mode so that every holder gets their principal back
+    pub fn can_dissolve(&self) -> bool {
+        match self.status {
+            LotteryStatus::Open | LotteryStatus::Cancelled => true,
+            LotteryStatus::Drawn | LotteryStatus::Settled => self.no_loss,
+            LotteryStatus::DrawCommitted => false,
+        }
+    }
+
+    /// Whether `now` falls in the transfer lock window of a lottery that is still running
+    pub fn is_transfer_locked(&self, now: i64) -> bool {
+        !self.is_finished()
+            && now >= self.transfer_lock_starts_at
+            && now < self.transfer_lock_ends_at
+    }
+
+    /// Whether tickets can be released from the lock, once a configured window is over or the lottery is finished
+    pub fn can_unlock_tickets(&self, now: i64) -> bool {
+        self.is_finished() || (self.transfer_lock_ends_at != 0 && now >= self.transfer_lock_ends_at)
+    }
+
+    /// Whether the royalty and a protocol fee of `protocol_fee_bps` together fit in a resale price
+    pub fn resale_fees_fit(&self, protocol_fee_bps: u16) -> bool {
+        self.seller_fee_basis_points as u32 + protocol_fee_bps as u32 <= 10_000
+    }
+
+    /// Price of the next ticket after a discount of `discount_bps`, if the lottery is priced
+    pub fn ticket_price(&self, discount_bps: u16) -> Option<u64> {
+        let price = self.pricing.as_ref()?.price(self.tickets_minted)?;
+        Some(price - (price as u128 * discount_bps as u128 / 10_000) as u64)
+    }
+
+    /// Whether the draw can no longer be revealed at `slot`
+    pub fn is_reveal_expired(&self, slot: u64) -> bool {
+        slot > self.draw_slot.saturating_add(REVEAL_WINDOW_SLOTS)
+    }
+
+    /// Whether players choose numbers instead of a single ticket being drawn
+    pub fn is_pick_mode(&self) -> bool {
+        self.pick_count > 0
+    }
+
+    /// Checks that `picks` are `pick_count` strictly increasing numbers in `1..=pick_max`
+    pub fn validate_picks(&self, picks: &[u8]) -> bool {
+        picks.len() == self.pick_count as usize
+            && picks.iter().all(|pick| *pick >= 1 && *pick <= self.pick_max)
+            && picks.windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    /// Number of `picks` that are among the winning numbers
+    pub fn count_matches(&self, picks: &[u8]) -> usize {
+        picks.iter().filter(|pick| self.winning_numbers.contains(pick)).count()
+    }
+
+    /// Prize of a registered ticket matching `matches` numbers: its tier share of the pool
+    /// split among the tier's registered winners
+    pub fn tier_prize(&self, matches: usize) -> u64 {
+        let winners = self.tier_winners[matches].max(1) as u128;
+        (self.prize_pool as u128 * self.tier_payout_bps[matches] as u128 / 10_000 / winners) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linear_step() -> PricingModel {
+        PricingModel::LinearStep { base_price: 100, step_size: 10, step_increment: 5 }
+    }
+
+    fn tiered() -> PricingModel {
+        PricingModel::Tiered {
+            tiers: vec![
+                PriceTier { sold_up_to: 10, price: 100 },
+                PriceTier { sold_up_to: 20, price: 200 },
+            ],
+        }
+    }
+
+    // An all-zero account: no pricing, nothing sold
+    fn lottery(pricing: Option<PricingModel>, tickets_minted: u64) -> LotteryStateData {
+        let data = vec![0u8; LotteryStateData::MAX_SIZE];
+        let mut lottery = LotteryStateData::deserialize(&mut data.as_slice()).unwrap();
+        lottery.pricing = pricing;
+        lottery.tickets_minted = tickets_minted;
+        lottery
+    }
+
+    #[test]
+    fn linear_step_price_rises_at_step_boundaries() {
+        let pricing = linear_step();
+        assert_eq!(pricing.price(0), Some(100));
+        assert_eq!(pricing.price(9), Some(100));
+        assert_eq!(pricing.price(10), Some(105));
+        assert_eq!(pricing.price(19), Some(105));
+        assert_eq!(pricing.price(20), Some(110));
+    }
+
+    #[test]
+    fn linear_step_price_overflow_is_none() {
+        let pricing = PricingModel::LinearStep { base_price: u64::MAX, step_size: 1, step_increment: 1 };
+        assert_eq!(pricing.price(0), Some(u64::MAX));
+        assert_eq!(pricing.price(1), None);
+    }
+
+    #[test]
+    fn tiered_price_switches_at_tier_bounds() {
+        let pricing = tiered();
+        assert_eq!(pricing.price(0), Some(100));
+        assert_eq!(pricing.price(9), Some(100));
+        assert_eq!(pricing.price(10), Some(200));
+        assert_eq!(pricing.price(19), Some(200));
+        // The last tier applies beyond its bound
+        assert_eq!(pricing.price(20), Some(200));
+        assert_eq!(pricing.price(1_000), Some(200));
+    }
+
+    #[test]
+    fn ticket_price_applies_discounts() {
+        let lottery = lottery(Some(linear_step()), 10);
+        assert_eq!(lottery.ticket_price(0), Some(105));
+        assert_eq!(lottery.ticket_price(2_000), Some(84));
+        assert_eq!(lottery.ticket_price(10_000), Some(0));
+    }
+
+    #[test]
+    fn unpriced_lottery_has_no_ticket_price() {
+        assert_eq!(lottery(None, 0).ticket_price(0), None);
+        assert_eq!(lottery(Some(PricingModel::Fixed { price: 50 }), 0).ticket_price(0), Some(50));
+    }
+
+    #[test]
+    fn resale_fees_fit_up_to_the_full_price() {
+        let mut lottery = lottery(None, 0);
+        lottery.seller_fee_basis_points = 7_000;
+        assert!(lottery.resale_fees_fit(0));
+        assert!(lottery.resale_fees_fit(3_000));
+        assert!(!lottery.resale_fees_fit(3_001));
+        assert!(!lottery.resale_fees_fit(u16::MAX));
+    }
+
+    #[test]
+    fn tickets_only_unlock_after_a_configured_window() {
+        let mut lottery = lottery(None, 0);
+        // No window yet: nothing to unlock from
+        assert!(!lottery.can_unlock_tickets(0));
+        assert!(!lottery.can_unlock_tickets(1_000));
+
+        lottery.transfer_lock_starts_at = 100;
+        lottery.transfer_lock_ends_at = 200;
+        assert!(!lottery.can_unlock_tickets(150));
+        assert!(lottery.can_unlock_tickets(200));
+
+        lottery.transfer_lock_ends_at = 0;
+        lottery.status = LotteryStatus::Settled;
+        assert!(lottery.can_unlock_tickets(0));
+    }
+}
\ No newline at end of file
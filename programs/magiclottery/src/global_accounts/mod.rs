@@ -1 +1,10 @@
-pub mod central_account;
\ No newline at end of file
+pub mod central_account;
+pub mod lottery_account;
+pub mod voucher_account;
+pub mod buyer_account;
+pub mod ticket_picks_account;
+pub mod listing_account;
+pub mod ticket_record_account;
+pub mod referrer_account;
+pub mod promo_account;
+pub mod yield_vault_account;
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+/// A giveaway of free tickets, redeemable with codes committed to by a Merkle root
+#[account]
+pub struct PromoCampaign {
+    pub lottery: Pubkey,
+    pub campaign_id: u64,
+    pub codes_root: [u8; 32],
+    /// Maximum number of tickets the campaign gives away
+    pub budget: u32,
+    pub redeemed: u32,
+}
+
+impl PromoCampaign {
+    pub const MAX_SIZE: usize = 32 + 8 + 32 + 4 + 4;
+}
+
+/// Marks a promo code as redeemed
+#[account]
+pub struct PromoRedemption {
+    pub lottery: Pubkey,
+    pub campaign: Pubkey,
+    pub code_hash: [u8; 32],
+    pub redeemer: Pubkey,
+}
+
+impl PromoRedemption {
+    pub const MAX_SIZE: usize = 32 + 32 + 32 + 32;
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+/// Referral rewards of a referrer in a lottery, held as lamports by the account itself
+#[account]
+pub struct ReferrerBalance {
+    pub lottery: Pubkey,
+    pub referrer: Pubkey,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+}
+
+impl ReferrerBalance {
+    pub const MAX_SIZE: usize = 32 + 32 + 8 + 8;
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::MAX_PICKS;
+
+#[account]
+pub struct TicketPicks {
+    pub lottery: Pubkey,
+    pub serial: u64,
+    pub picks: Vec<u8>,
+    /// Whether the ticket is registered as a winner of its tier
+    pub registered: bool,
+}
+
+impl TicketPicks {
+    pub const MAX_SIZE: usize = 32 + 8 + (4 + MAX_PICKS) + 1;
+}
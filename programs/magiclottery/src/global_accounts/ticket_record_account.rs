@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::{LotteryStateData, RefundPolicy};
+
+#[account]
+pub struct TicketRecord {
+    pub lottery: Pubkey,
+    pub serial: u64,
+    pub payer: Pubkey,
+    /// The wallet the ticket was minted to, differs from the payer for gifts
+    pub recipient: Pubkey,
+    pub price: u64,
+    /// The lottery refund policy at the time of purchase
+    pub refund_policy: RefundPolicy,
+    /// End of the lock window the ticket was released from by `unlock_ticket`, 0 if never unlocked
+    pub unlocked_for: i64,
+    /// The wallet expected to hold the ticket, updated whenever its owner goes through this program
+    pub holder: Pubkey,
+}
+
+impl TicketRecord {
+    pub const MAX_SIZE: usize = 32 + 8 + 32 + 32 + 8 + 1 + 8 + 32;
+
+    /// Whether the leaf owner `owner` can use the ticket of `lottery` at `now`
+    ///
+    /// Bubblegum v1 can't freeze leaves, so owners can always transfer tickets directly.
+    /// Outside the lock window the leaf owner checked by Bubblegum is who the ticket
+    /// belongs to. Inside it, only the recorded holder qualifies, so a ticket moved
+    /// through Bubblegum can't be used by its new owner before the window ends. An
+    /// unlock only exempts the ticket from the window it was released from.
+    pub fn is_usable_by(&self, owner: &Pubkey, lottery: &LotteryStateData, now: i64) -> bool {
+        !lottery.is_transfer_locked(now)
+            || self.holder == *owner
+            || self.unlocked_for == lottery.transfer_lock_ends_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(holder: Pubkey, unlocked_for: i64) -> TicketRecord {
+        let data = vec![0u8; TicketRecord::MAX_SIZE];
+        let mut record = TicketRecord::deserialize(&mut data.as_slice()).unwrap();
+        record.holder = holder;
+        record.unlocked_for = unlocked_for;
+        record
+    }
+
+    // An open lottery locking tickets from 100 until 200
+    fn lottery() -> LotteryStateData {
+        let data = vec![0u8; LotteryStateData::MAX_SIZE];
+        let mut lottery = LotteryStateData::deserialize(&mut data.as_slice()).unwrap();
+        lottery.transfer_lock_starts_at = 100;
+        lottery.transfer_lock_ends_at = 200;
+        lottery
+    }
+
+    #[test]
+    fn only_the_recorded_holder_uses_a_locked_ticket() {
+        let holder = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let record = record(holder, 0);
+        let lottery = lottery();
+
+        assert!(record.is_usable_by(&holder, &lottery, 150));
+        assert!(!record.is_usable_by(&other, &lottery, 150));
+        // Outside the window Bubblegum's owner check is enough
+        assert!(record.is_usable_by(&other, &lottery, 50));
+        assert!(record.is_usable_by(&other, &lottery, 200));
+    }
+
+    #[test]
+    fn unlocks_only_exempt_their_own_window() {
+        let other = Pubkey::new_unique();
+        let mut lottery = lottery();
+
+        let record = record(Pubkey::new_unique(), 200);
+        assert!(record.is_usable_by(&other, &lottery, 150));
+
+        // A later window locks the ticket again
+        lottery.transfer_lock_starts_at = 300;
+        lottery.transfer_lock_ends_at = 400;
+        assert!(!record.is_usable_by(&other, &lottery, 350));
+    }
+}
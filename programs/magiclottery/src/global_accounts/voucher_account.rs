@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+#[account]
+pub struct VoucherRecord {
+    pub lottery: Pubkey,
+    pub buyer: Pubkey,
+    pub nonce: u64,
+    pub redeemed: u32,
+}
+
+impl VoucherRecord {
+    pub const MAX_SIZE: usize = 32 + 32 + 8 + 4;
+}
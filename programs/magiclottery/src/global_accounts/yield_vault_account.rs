@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+/// Principal and yield accounting of a no-loss lottery
+///
+/// Ticket principal sits in the lottery vault or is deposited into the yield source
+/// position; whatever the vault holds above its liquid principal is yield funding prizes.
+#[account]
+pub struct YieldVault {
+    pub lottery: Pubkey,
+    pub yield_program: Pubkey,
+    pub position: Pubkey,
+    /// Refundable principal of the live tickets
+    pub principal: u64,
+    /// Part of the principal deposited into the yield source
+    pub deposited: u64,
+    /// Total yield withdrawn from the yield source into the lottery vault
+    pub harvested_yield: u64,
+}
+
+impl YieldVault {
+    pub const MAX_SIZE: usize = 32 + 32 + 32 + 8 + 8 + 8;
+
+    /// Principal held in the lottery vault, available for refunds
+    pub fn liquid_principal(&self) -> u64 {
+        self.principal.saturating_sub(self.deposited)
+    }
+
+    /// Part of the vault balance above rent that isn't principal and can be paid out as prizes
+    pub fn prize_funds(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.liquid_principal())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yield_vault(principal: u64, deposited: u64) -> YieldVault {
+        YieldVault {
+            lottery: Pubkey::default(),
+            yield_program: Pubkey::default(),
+            position: Pubkey::default(),
+            principal,
+            deposited,
+            harvested_yield: 0,
+        }
+    }
+
+    #[test]
+    fn liquid_principal_excludes_deposits() {
+        assert_eq!(yield_vault(1_000, 0).liquid_principal(), 1_000);
+        assert_eq!(yield_vault(1_000, 600).liquid_principal(), 400);
+        assert_eq!(yield_vault(1_000, 1_000).liquid_principal(), 0);
+    }
+
+    #[test]
+    fn liquid_principal_never_underflows() {
+        assert_eq!(yield_vault(300, 500).liquid_principal(), 0);
+    }
+
+    #[test]
+    fn prize_funds_exclude_liquid_principal() {
+        let vault = yield_vault(1_000, 600);
+        assert_eq!(vault.prize_funds(400), 0);
+        assert_eq!(vault.prize_funds(650), 250);
+    }
+
+    #[test]
+    fn prize_funds_are_the_whole_balance_once_principal_is_deposited() {
+        assert_eq!(yield_vault(1_000, 1_000).prize_funds(250), 250);
+        assert_eq!(yield_vault(0, 0).prize_funds(250), 250);
+    }
+
+    #[test]
+    fn prize_funds_saturate_when_principal_is_missing() {
+        assert_eq!(yield_vault(1_000, 0).prize_funds(400), 0);
+    }
+}
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::listing_account::TicketListing;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketResold;
+use crate::clones::program_ids::*;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::instructions::TransferCpiBuilder;
+use crate::{CENTRAL_AUTHORITY_SEED, LISTING_SEED, LOTTERY_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyListedTicket<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::LotteryNotOpen
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = lottery,
+        has_one = merkle_tree,
+        seeds = [LISTING_SEED, lottery.key().as_ref(), listing.seller.as_ref(), &listing.serial.to_le_bytes()],
+        bump
+    )]
+    pub listing: Account<'info, TicketListing>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &listing.serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    /// CHECK: The owner of the listed ticket, checked against the listing
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the protocol fee, only required when a fee is charged
+    #[account(
+        mut,
+        address = central_authority.protocol_treasury @ MyError::InvalidTreasury,
+        constraint = protocol_treasury.key() != Pubkey::default() @ MyError::TreasuryNotSet
+    )]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: This account is modified in the downstream program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub tree_config: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys a listed ticket at its asking price
+///
+/// The price is split between the ticket creators, following the lottery royalty
+/// settings, the protocol treasury and the seller, who also receives any rounding
+/// dust. The protocol treasury is only passed when a protocol fee is charged, and the
+/// sale fails if it is charged without a treasury set, or if the royalty and protocol
+/// fee together exceed the price. The ticket is then
+/// transferred to the buyer by the listing PDA and the buyer is recorded as the
+/// ticket holder, so the buyer can claim it during the next lock window.
+///
+/// The remaining accounts hold the Merkle proof followed by one writable account
+/// per lottery creator, in the order of the royalty settings.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `root` - The root hash of the merkle tree
+/// * `nonce` - The nonce of the leaf
+/// * `index` - The index of the leaf in the merkle tree
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn buy_listed_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyListedTicket<'info>>,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    msg!("buying listed ticket");
+
+    let lottery = &ctx.accounts.lottery;
+    let listing = &ctx.accounts.listing;
+    let price = listing.price;
+    let serial = listing.serial;
+    require!(!lottery.is_transfer_locked(Clock::get()?.unix_timestamp), MyError::TicketsLocked);
+    require!(lottery.resale_fees_fit(ctx.accounts.central_authority.protocol_fee_bps), MyError::ResaleFeesTooHigh);
+
+    // The creators follow the proof
+    let (leaf, creator_accounts) = ticket_leaf(
+        lottery,
+        &ctx.accounts.merkle_tree.key(),
+        serial,
+        ctx.remaining_accounts,
+        lottery.creators.len(),
+    )?;
+    require!(
+        creator_accounts.iter().zip(&lottery.creators)
+            .all(|(account, creator)| account.key() == creator.address),
+        MyError::InvalidCreatorAccounts
+    );
+
+    // Work out the royalty and protocol fee
+    let royalty = (price as u128 * lottery.seller_fee_basis_points as u128 / 10_000) as u64;
+    let protocol_fee = (price as u128 * ctx.accounts.central_authority.protocol_fee_bps as u128 / 10_000) as u64;
+
+    let mut royalty_paid: u64 = 0;
+    for (account, creator) in creator_accounts.iter().zip(&lottery.creators) {
+        let amount = (royalty as u128 * creator.share as u128 / 100) as u64;
+        if amount == 0 {
+            continue;
+        }
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            amount,
+        )?;
+        royalty_paid += amount;
+    }
+
+    if protocol_fee > 0 {
+        let protocol_treasury = ctx.accounts.protocol_treasury.as_ref()
+            .ok_or(MyError::TreasuryNotSet)?;
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: protocol_treasury.to_account_info(),
+                },
+            ),
+            protocol_fee,
+        )?;
+    }
+
+    let seller_amount = price
+        .checked_sub(royalty_paid)
+        .and_then(|amount| amount.checked_sub(protocol_fee))
+        .ok_or(MyError::MathOverflow)?;
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        seller_amount,
+    )?;
+
+    // Transfer the ticket to the buyer, signed by the listing PDA as leaf delegate
+    let lottery_key = lottery.key();
+    let serial_bytes = serial.to_le_bytes();
+    let bump_seed = [ctx.bumps.listing];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        LISTING_SEED,
+        lottery_key.as_ref(),
+        ctx.accounts.seller.key.as_ref(),
+        &serial_bytes,
+        &bump_seed,
+    ]];
+
+    TransferCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.seller.to_account_info(), false)
+        .leaf_delegate(&ctx.accounts.listing.to_account_info(), true)
+        .new_leaf_owner(&ctx.accounts.buyer.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .invoke_signed(signer_seeds)?;
+
+    ctx.accounts.ticket_record.holder = ctx.accounts.buyer.key();
+
+    emit_cpi!(TicketResold {
+        lottery: lottery_key,
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        serial,
+        price,
+        royalty: royalty_paid,
+        protocol_fee,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,29 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{Transfer, transfer};
 use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::voucher_account::VoucherRecord;
+use crate::global_accounts::buyer_account::BuyerRecord;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::global_accounts::referrer_account::ReferrerBalance;
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::utils::voucher::{PurchaseVoucher, verify_voucher_signature};
+use crate::utils::allowlist::{AllowlistProof, enforce_allowlist};
+use crate::utils::discount::holder_discount_bps;
+use crate::utils::mint::{MintTicketAccounts, mint_ticket};
 use crate::errors::errors::MyError;
+use crate::events::lottery_events::{ReferralCredited, TicketPurchased};
 use crate::clones::program_ids::*;
-use mpl_bubblegum::instructions::MintToCollectionV1CpiBuilder;
-use mpl_bubblegum::types::{Collection, MetadataArgs, TokenProgramVersion, TokenStandard};
-use crate::CENTRAL_AUTHORITY_SEED;
+use anchor_spl::token::TokenAccount;
+use crate::{BUYER_SEED, CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PICKS_SEED, REFERRER_SEED, TICKET_LOCK_SEED, TICKET_SEED, VAULT_SEED, VOUCHER_SEED, YIELD_VAULT_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(
+    deposit_amount: u64,
+    voucher: Option<PurchaseVoucher>,
+)]
 pub struct BuyTicket<'info> {
     #[account(mut, signer)]
     pub payer: Signer<'info>,
@@ -15,14 +31,103 @@ pub struct BuyTicket<'info> {
     #[account(
         constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
     )]
-    pub authorizer_wallet: Signer<'info>,
+    pub authorizer_wallet: Option<Signer<'info>>,
 
     #[account(
         seeds = [CENTRAL_AUTHORITY_SEED],
-        bump
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
     )]
     pub central_authority: Account<'info, CentralStateData>,
 
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, collection_mint.key().as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::LotteryNotOpen
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TicketPicks::MAX_SIZE,
+        seeds = [PICKS_SEED, lottery.key().as_ref(), &(lottery.tickets_minted + 1).to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Option<Account<'info, TicketPicks>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TicketRecord::MAX_SIZE,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &(lottery.tickets_minted + 1).to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoucherRecord::MAX_SIZE,
+        seeds = [
+            VOUCHER_SEED,
+            lottery.key().as_ref(),
+            payer.key().as_ref(),
+            &voucher.as_ref().map_or(0, |v| v.nonce).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub voucher_record: Option<Account<'info, VoucherRecord>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BuyerRecord::MAX_SIZE,
+        seeds = [BUYER_SEED, lottery.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// CHECK: The wallet credited with the referral share, it doesn't need to sign
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReferrerBalance::MAX_SIZE,
+        seeds = [
+            REFERRER_SEED,
+            lottery.key().as_ref(),
+            referrer.as_ref().map_or(Pubkey::default(), |r| r.key()).as_ref(),
+        ],
+        bump
+    )]
+    pub referrer_balance: Option<Account<'info, ReferrerBalance>>,
+
+    /// The principal accounting of the lottery, required in no-loss mode
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    /// The payer's token account holding an NFT of a discounted partner collection
+    #[account(
+        constraint = holder_token_account.owner == payer.key() @ MyError::InvalidHolderProof,
+        constraint = holder_token_account.amount == 1 @ MyError::InvalidHolderProof
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: The metadata of the held NFT, checked in the instruction
+    pub holder_metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The instructions sysvar, used to verify the voucher signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
     /// CHECK: This account is checked in the instruction
     #[account(mut)]
     pub tree_config: UncheckedAccount<'info>,
@@ -30,11 +135,19 @@ pub struct BuyTicket<'info> {
     /// CHECK: This account is neither written to nor read from.
     pub leaf_owner: AccountInfo<'info>,
 
+    /// CHECK: The PDA set as leaf delegate until the ticket is unlocked, it never signs and restricts nothing
+    #[account(
+        seeds = [TICKET_LOCK_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub ticket_lock: UncheckedAccount<'info>,
+
     #[account(mut)]
     /// CHECK: unsafe
     pub merkle_tree: UncheckedAccount<'info>,
 
-    // pub tree_delegate: Signer<'info>,
+    /// The tree delegate set with `set_tree_delegate`, required instead of the central authority once set
+    pub tree_delegate: Option<Signer<'info>>,
 
     /// CHECK: This account is checked in the instruction
     pub collection_mint: UncheckedAccount<'info>,
@@ -46,7 +159,11 @@ pub struct BuyTicket<'info> {
     /// CHECK: This account is checked in the instruction
     pub edition_account: UncheckedAccount<'info>,
     /// CHECK: This is the PDA that will receive the payment
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
     pub payment_receiver: UncheckedAccount<'info>,
 
     /// CHECK: This is just used as a signing PDA.
@@ -59,30 +176,164 @@ pub struct BuyTicket<'info> {
     
 }
 
+/// Identifies the ticket minted by `buy_ticket`, returned to the caller as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TicketReceipt {
+    pub serial: u64,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,
+    pub asset_id: Pubkey,
+}
+
 /// Buys a ticket for the lottery by minting a compressed NFT to the specified collection.
 ///
+/// The ticket metadata is generated from the lottery: the name is `<symbol> #<serial>`
+/// and the URI `<base_uri>/<serial>.json`, where the serial is the lottery's mint counter.
+///
+/// The authorizer wallet co-signature is only required when the lottery was created
+/// with `requires_cosign`; public lotteries can be bought from permissionlessly.
+/// Instead of co-signing, the backend can issue a `PurchaseVoucher` signed with the
+/// authorizer key, verified through an Ed25519 program instruction placed right
+/// before this one. Each voucher can be redeemed up to `quantity` times; redemptions
+/// are counted per buyer and nonce, so vouchers of different buyers never share a count.
+///
+/// Allowlisted lotteries additionally require a Merkle proof that the payer is on the
+/// allowlist; a per-address cap encoded in the leaf is enforced with the buyer record.
+///
+/// When minting has been delegated, the tree delegate must sign and is passed as
+/// `tree_creator_or_delegate`; otherwise the central authority signs as tree creator.
+///
+/// Tickets are minted into the lottery's registered trees in order; once the current
+/// tree is full, as reported by its Bubblegum tree config, the lottery rolls over to
+/// the next one, which the caller must pass along with the full tree configs it skips.
+///
+/// When the lottery has a pricing model, the price of the ticket is computed from the
+/// number of tickets sold so far and charged instead of `payment_amount`, failing if it
+/// exceeds the buyer's `max_price`. Holders of an NFT from one of the lottery's discount
+/// collections pass their token account and the NFT metadata to pay the discounted price.
+/// Discounts only apply to priced lotteries: passing the holder accounts to an unpriced
+/// lottery or with a voucher fails. Voucher purchases pay the voucher price.
+///
+/// In no-loss mode the payment is added to the lottery principal in the yield vault.
+///
+/// In pick mode the player's numbers are validated against the lottery configuration
+/// and stored in the ticket picks PDA keyed by the ticket serial.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context of the instruction, containing all necessary accounts.
-/// * `name` - The name of the NFT ticket.
-/// * `symbol` - The symbol of the NFT ticket.
-/// * `uri` - The URI pointing to the metadata of the NFT ticket.
-/// * `seller_fee_basis_points` - The royalty fee in basis points (100 = 1%).
 /// * `payment_amount` - The amount of SOL to transfer.
-/// * `payment_receiver_key` - The key of the PDA that will receive the payment.
+/// * `voucher` - An optional purchase voucher signed by the authorizer wallet.
+/// * `allowlist_proof` - The allowlist proof, required when the lottery has an allowlist root.
+/// * `picks` - The player's numbers in pick mode, empty for raffles.
+/// * `max_price` - The highest price the buyer accepts when the lottery has a pricing model.
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if the ticket is successfully purchased, or an error otherwise.
+/// Returns the `TicketReceipt` of the minted ticket if the purchase succeeds, or an error otherwise.
 pub fn buy_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>,
-        name: String,
-        symbol: String,
-        uri: String,
-        seller_fee_basis_points: u16,
-        deposit_amount: u64
-    ) -> Result<()> {
+        deposit_amount: u64,
+        voucher: Option<PurchaseVoucher>,
+        allowlist_proof: Option<AllowlistProof>,
+        picks: Vec<u8>,
+        max_price: u64,
+    ) -> Result<TicketReceipt> {
         msg!("buying ticket for collection");
 
+        // Priced lotteries charge the price at the current ticket count, less the discount of partner collection holders
+        let mut deposit_amount = deposit_amount;
+        let has_holder_accounts = ctx.accounts.holder_token_account.is_some() || ctx.accounts.holder_metadata.is_some();
+        require!(
+            !has_holder_accounts || (voucher.is_none() && ctx.accounts.lottery.pricing.is_some()),
+            MyError::DiscountNotApplicable
+        );
+        if voucher.is_none() && ctx.accounts.lottery.pricing.is_some() {
+            let discount_bps = match (&ctx.accounts.holder_token_account, &ctx.accounts.holder_metadata) {
+                (Some(token_account), Some(metadata)) => {
+                    holder_discount_bps(&ctx.accounts.lottery, token_account, &metadata.to_account_info())?
+                }
+                (None, None) => 0,
+                _ => return err!(MyError::InvalidHolderProof),
+            };
+            let price = ctx.accounts.lottery.ticket_price(discount_bps).ok_or(MyError::MathOverflow)?;
+            require!(price <= max_price, MyError::PriceAboveMax);
+            deposit_amount = price;
+        }
+
+        if let Some(voucher) = voucher {
+            let instructions_sysvar = ctx.accounts.instructions_sysvar.as_ref()
+                .ok_or(MyError::MissingVoucherAccounts)?;
+            verify_voucher_signature(
+                &instructions_sysvar.to_account_info(),
+                &ctx.accounts.central_authority.authorizer_wallet,
+                &voucher,
+            )?;
+
+            require_keys_eq!(voucher.buyer, ctx.accounts.payer.key(), MyError::InvalidVoucher);
+            require_keys_eq!(voucher.lottery, ctx.accounts.lottery.key(), MyError::InvalidVoucher);
+            require!(Clock::get()?.unix_timestamp <= voucher.expiry, MyError::VoucherExpired);
+            require_eq!(deposit_amount, voucher.price, MyError::VoucherPriceMismatch);
+
+            // Track redemptions per nonce to prevent replays
+            let voucher_record = ctx.accounts.voucher_record.as_mut()
+                .ok_or(MyError::MissingVoucherAccounts)?;
+            require!(voucher_record.redeemed < voucher.quantity, MyError::VoucherExhausted);
+            voucher_record.lottery = voucher.lottery;
+            voucher_record.buyer = voucher.buyer;
+            voucher_record.nonce = voucher.nonce;
+            voucher_record.redeemed += 1;
+        } else if ctx.accounts.lottery.requires_cosign {
+            require!(ctx.accounts.authorizer_wallet.is_some(), MyError::CosignRequired);
+        }
+
+        // Enforce the allowlist and its per-address cap
+        let lottery_key = ctx.accounts.lottery.key();
+        enforce_allowlist(
+            &ctx.accounts.lottery,
+            lottery_key,
+            ctx.accounts.payer.key(),
+            allowlist_proof,
+            ctx.accounts.buyer_record.as_deref_mut(),
+        )?;
+
+        require!(ctx.accounts.lottery.validate_picks(&picks), MyError::InvalidPicks);
+
+        // Credit the referral share to the referrer balance, the rest goes to the vault
+        let mut referral_amount = 0;
+        if let Some(referrer) = &ctx.accounts.referrer {
+            require_keys_neq!(referrer.key(), ctx.accounts.payer.key(), MyError::SelfReferral);
+            let referrer_balance = ctx.accounts.referrer_balance.as_mut()
+                .ok_or(MyError::MissingReferrerBalance)?;
+
+            referral_amount = (deposit_amount as u128 * ctx.accounts.lottery.referral_bps as u128 / 10_000) as u64;
+            referrer_balance.lottery = ctx.accounts.lottery.key();
+            referrer_balance.referrer = referrer.key();
+            referrer_balance.total_earned = referrer_balance.total_earned
+                .checked_add(referral_amount)
+                .ok_or(MyError::MathOverflow)?;
+
+            if referral_amount > 0 {
+                transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: referrer_balance.to_account_info(),
+                        },
+                    ),
+                    referral_amount,
+                )?;
+            }
+
+            emit_cpi!(ReferralCredited {
+                lottery: ctx.accounts.lottery.key(),
+                referrer: referrer.key(),
+                payer: ctx.accounts.payer.key(),
+                amount: referral_amount,
+            });
+        }
+        let vault_amount = deposit_amount - referral_amount;
+
         // Transfer SOL from payer to payment receiver PDA
         transfer(
             CpiContext::new(
@@ -92,55 +343,61 @@ pub fn buy_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>,
                     to: ctx.accounts.payment_receiver.to_account_info(),
                 },
             ),
-            deposit_amount,
+            vault_amount,
         )?;
 
-        // Prepare the signer seeds for the CPI call
-        let bump_seed = [ctx.bumps.central_authority];
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            CENTRAL_AUTHORITY_SEED,
-            &bump_seed,
-        ]];
-        
-        // Build and invoke the MintToCollectionV1 CPI with the prepared builder and signer seeds
-        MintToCollectionV1CpiBuilder::new(
-            &ctx.accounts.bubblegum_program.to_account_info(),
-        )
-            .tree_config(&ctx.accounts.tree_config.to_account_info())
-            .leaf_owner(&ctx.accounts.leaf_owner.to_account_info())
-            .leaf_delegate(&ctx.accounts.leaf_owner.to_account_info())
-            .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
-            .payer(&ctx.accounts.payer.to_account_info())
-            .tree_creator_or_delegate(&ctx.accounts.central_authority.to_account_info())
-            .collection_authority(&ctx.accounts.central_authority.to_account_info())
-            .collection_authority_record_pda(Some(&ctx.accounts.bubblegum_program.to_account_info()))
-            .collection_mint(&ctx.accounts.collection_mint.to_account_info())
-            .collection_metadata(&ctx.accounts.collection_metadata.to_account_info())
-            .collection_edition(&ctx.accounts.edition_account.to_account_info())
-            .bubblegum_signer(&ctx.accounts.bubblegum_signer.to_account_info())
-            .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
-            .compression_program(&ctx.accounts.compression_program.to_account_info())
-            .token_metadata_program(&ctx.accounts.token_metadata_program.to_account_info())
-            .system_program(&ctx.accounts.system_program.to_account_info())
-            .metadata(
-                MetadataArgs {
-                    name,
-                    symbol,
-                    uri,
-                    creators: vec![],
-                    seller_fee_basis_points,
-                    primary_sale_happened: false,
-                    is_mutable: false,
-                    edition_nonce: Some(0),
-                    uses: None,
-                    collection: Some(Collection {
-                        verified: true,
-                        key: ctx.accounts.collection_mint.key(),
-                    }),
-                    token_program_version: TokenProgramVersion::Original,
-                    token_standard: Some(TokenStandard::NonFungible),
-                }
-            )
-            .invoke_signed(signer_seeds)?;
-        Ok(())
+        // In no-loss mode the payment is principal, refunded in full on dissolve, and free
+        // entries would share the yield without putting anything at work
+        if ctx.accounts.lottery.no_loss {
+            require!(vault_amount > 0, MyError::ZeroDeposit);
+            let yield_vault = ctx.accounts.yield_vault.as_mut()
+                .ok_or(MyError::MissingYieldVault)?;
+            yield_vault.principal = yield_vault.principal
+                .checked_add(vault_amount)
+                .ok_or(MyError::MathOverflow)?;
+        }
+
+        // Mint the ticket, only what reached the vault can be refunded
+        let accounts = MintTicketAccounts {
+            payer: ctx.accounts.payer.to_account_info(),
+            central_authority: ctx.accounts.central_authority.to_account_info(),
+            leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
+            ticket_lock: ctx.accounts.ticket_lock.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            tree_config: ctx.accounts.tree_config.to_account_info(),
+            tree_delegate: ctx.accounts.tree_delegate.as_ref().map(|tree_delegate| tree_delegate.to_account_info()),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            edition_account: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let receipt = mint_ticket(
+            &mut ctx.accounts.lottery,
+            lottery_key,
+            accounts,
+            ctx.remaining_accounts,
+            ctx.bumps.central_authority,
+            ctx.accounts.ticket_picks.as_deref_mut(),
+            picks,
+            &mut ctx.accounts.ticket_record,
+            vault_amount,
+        )?;
+
+        emit_cpi!(TicketPurchased {
+            lottery: ctx.accounts.lottery.key(),
+            payer: ctx.accounts.payer.key(),
+            leaf_owner: ctx.accounts.leaf_owner.key(),
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            serial: receipt.serial,
+            leaf_index: receipt.leaf_index,
+            asset_id: receipt.asset_id,
+            price: deposit_amount,
+        });
+
+        Ok(receipt)
     }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::listing_account::TicketListing;
+use crate::events::lottery_events::ListingCancelled;
+use crate::{LISTING_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = lottery,
+        seeds = [LISTING_SEED, lottery.key().as_ref(), seller.key().as_ref(), &listing.serial.to_le_bytes()],
+        bump
+    )]
+    pub listing: Account<'info, TicketListing>,
+}
+
+/// Cancels a ticket listing
+///
+/// The listing account is closed, which is enough to stop `buy_listed_ticket`. The
+/// listing PDA stays the leaf delegate, but it can't sign for a closed listing and
+/// the seller can still transfer or relist the ticket as its owner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    msg!("cancelling ticket listing");
+
+    emit_cpi!(ListingCancelled {
+        lottery: ctx.accounts.lottery.key(),
+        seller: ctx.accounts.seller.key(),
+        serial: ctx.accounts.listing.serial,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::LotteryCancelled;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelLottery<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = matches!(lottery.status, LotteryStatus::Open | LotteryStatus::DrawCommitted) @ MyError::LotteryNotCancellable
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelExpiredDraw<'info> {
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = lottery.status == LotteryStatus::DrawCommitted @ MyError::DrawNotCommitted
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Cancels a lottery before its draw is revealed
+///
+/// Ticket sales and the draw stop; ticket holders can still dissolve their tickets.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn cancel_lottery(ctx: Context<CancelLottery>) -> Result<()> {
+    msg!("cancelling lottery");
+
+    ctx.accounts.lottery.status = LotteryStatus::Cancelled;
+
+    emit_cpi!(LotteryCancelled {
+        lottery: ctx.accounts.lottery.key(),
+    });
+
+    Ok(())
+}
+
+/// Cancels a lottery whose draw was not revealed in time
+///
+/// Anyone can call it once `REVEAL_WINDOW_SLOTS` have passed after the draw slot, so
+/// an operator withholding an unfavourable reveal can't keep the funds locked. Ticket
+/// holders can then dissolve their tickets.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn cancel_expired_draw(ctx: Context<CancelExpiredDraw>) -> Result<()> {
+    msg!("cancelling expired draw");
+
+    require!(
+        ctx.accounts.lottery.is_reveal_expired(Clock::get()?.slot),
+        MyError::RevealNotExpired
+    );
+    ctx.accounts.lottery.status = LotteryStatus::Cancelled;
+
+    emit_cpi!(LotteryCancelled {
+        lottery: ctx.accounts.lottery.key(),
+    });
+
+    Ok(())
+}
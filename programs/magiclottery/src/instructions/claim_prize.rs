@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus, RefundPolicy};
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::PrizeClaimed;
+use crate::clones::program_ids::*;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::hash::hash_metadata;
+use mpl_bubblegum::instructions::{BurnCpiBuilder, UnverifyCollectionCpiBuilder};
+use mpl_token_metadata::accounts::{ MasterEdition, Metadata as MetadataAccount };
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PICKS_SEED, TICKET_SEED, VAULT_SEED, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(serial: u64)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Drawn @ MyError::LotteryNotDrawn
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    #[account(
+        mut,
+        close = ticket_payer,
+        seeds = [PICKS_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Option<Account<'info, TicketPicks>>,
+
+    #[account(
+        mut,
+        close = ticket_payer,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    /// CHECK: The wallet that paid for the ticket, receiving the record rent
+    #[account(
+        mut,
+        address = ticket_record.payer
+    )]
+    pub ticket_payer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: The current leaf delegate, checked against the leaf by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: This account is modified in the downstream program
+    #[account(
+        mut,
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = lottery.collection_mint)]
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = MetadataAccount::find_pda(&lottery.collection_mint).0,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = MasterEdition::find_pda(&lottery.collection_mint).0)]
+    pub edition_account: UncheckedAccount<'info>,
+
+    /// CHECK: This is just used as a signing PDA.
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claims the prize of a winning ticket by burning it
+///
+/// The ticket metadata is rebuilt from the serial, so a successful burn proves the
+/// signer owned ticket `serial` of this lottery, and burning it prevents a second
+/// claim. Raffle winners receive the whole prize pool; in pick mode tickets registered
+/// with `register_win` split the tier share of the pool matching their number of
+/// matches once the registration window is over, capped by what is left in the
+/// vault. As in `disolve_ticket`, the ticket is unverified from the collection before
+/// the burn to keep the collection size accurate. The ticket record, and in pick mode
+/// the ticket picks, are closed and their rent returned to the payer.
+///
+/// During the transfer lock window the leaf owner must be the holder recorded on the
+/// ticket record, unless the ticket was unlocked by `unlock_ticket` for that window. Bubblegum can't
+/// freeze the ticket, so a ticket moved through Bubblegum directly can't be claimed
+/// by its new owner until the window ends; outside the window any leaf owner can
+/// claim it.
+///
+/// In no-loss mode prizes are paid out of the yield only, and the ticket principal
+/// is refunded along with the prize following the ticket's refund policy.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
+/// * `root` - The root hash of the merkle tree
+/// * `nonce` - The nonce of the leaf
+/// * `index` - The index of the leaf in the merkle tree
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn claim_prize<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimPrize<'info>>,
+    serial: u64,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    msg!("claiming lottery prize");
+
+    let lottery = &ctx.accounts.lottery;
+    require!(
+        ctx.accounts.ticket_record.is_usable_by(
+            ctx.accounts.leaf_owner.key,
+            lottery,
+            Clock::get()?.unix_timestamp,
+        ),
+        MyError::TicketLockBypassed
+    );
+
+    // Work out the prize of the ticket
+    let (matches, amount) = if lottery.is_pick_mode() {
+        require!(Clock::get()?.unix_timestamp >= lottery.claims_open_at, MyError::ClaimsNotOpen);
+        let ticket_picks = ctx.accounts.ticket_picks.as_ref()
+            .ok_or(MyError::MissingTicketPicks)?;
+        require!(ticket_picks.registered, MyError::WinNotRegistered);
+        let matches = lottery.count_matches(&ticket_picks.picks);
+        (matches as u8, lottery.tier_prize(matches))
+    } else {
+        require_eq!(serial, lottery.winning_ticket, MyError::NoPrize);
+        (0, lottery.prize_pool)
+    };
+    require!(amount > 0, MyError::NoPrize);
+
+    // Tier payouts are first come first served once the vault runs dry
+    let mut available = ctx.accounts.vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_ref()
+            .ok_or(MyError::MissingYieldVault)?;
+        available = yield_vault.prize_funds(available);
+    }
+    let amount = amount.min(available);
+    require!(amount > 0, MyError::PrizePoolExhausted);
+
+    // Burn the ticket, proving ownership of the recomputed leaf
+    let (leaf, _) = ticket_leaf(lottery, &ctx.accounts.merkle_tree.key(), serial, ctx.remaining_accounts, 0)?;
+    let mut metadata = leaf.metadata;
+
+    let bump_seed = [ctx.bumps.central_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    UnverifyCollectionCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.leaf_owner.to_account_info())
+        .leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .payer(&ctx.accounts.leaf_owner.to_account_info())
+        .tree_creator_or_delegate(&ctx.accounts.central_authority.to_account_info())
+        .collection_authority(&ctx.accounts.central_authority.to_account_info())
+        .collection_authority_record_pda(Some(&ctx.accounts.bubblegum_program.to_account_info()))
+        .collection_mint(&ctx.accounts.collection_mint.to_account_info())
+        .collection_metadata(&ctx.accounts.collection_metadata.to_account_info())
+        .collection_edition(&ctx.accounts.edition_account.to_account_info())
+        .bubblegum_signer(&ctx.accounts.bubblegum_signer.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .token_metadata_program(&ctx.accounts.token_metadata_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .metadata(metadata.clone())
+        .invoke_signed(signer_seeds)?;
+
+    if let Some(collection) = metadata.collection.as_mut() {
+        collection.verified = false;
+    }
+    let data_hash = hash_metadata(&metadata)?;
+
+    BurnCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.leaf_owner.to_account_info(), true)
+        .leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info(), false)
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .invoke()?;
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.collection_size = lottery.collection_size.saturating_sub(1);
+
+    // Pay the prize out of the vault
+    let lottery_key = lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.leaf_owner.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    // No-loss winners also get their principal back
+    let mut principal_refund = 0;
+    if lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_mut()
+            .ok_or(MyError::MissingYieldVault)?;
+        principal_refund = ctx.accounts.ticket_record.price;
+        require!(principal_refund <= yield_vault.liquid_principal(), MyError::InsufficientLiquidity);
+        yield_vault.principal -= principal_refund;
+
+        let refund_receiver = match ctx.accounts.ticket_record.refund_policy {
+            RefundPolicy::Holder => ctx.accounts.leaf_owner.to_account_info(),
+            RefundPolicy::Payer => ctx.accounts.ticket_payer.to_account_info(),
+        };
+
+        if principal_refund > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: refund_receiver,
+                    },
+                    signer_seeds,
+                ),
+                principal_refund,
+            )?;
+        }
+    }
+
+    emit_cpi!(PrizeClaimed {
+        lottery: lottery_key,
+        winner: ctx.accounts.leaf_owner.key(),
+        serial,
+        matches,
+        amount,
+        principal_refund,
+    });
+
+    Ok(())
+}
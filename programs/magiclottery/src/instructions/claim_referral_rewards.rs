@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::referrer_account::ReferrerBalance;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::ReferralRewardsClaimed;
+use crate::REFERRER_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = referrer,
+        seeds = [REFERRER_SEED, referrer_balance.lottery.as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_balance: Account<'info, ReferrerBalance>,
+}
+
+/// Withdraws the referral rewards credited to a referrer
+///
+/// Everything the referrer balance account holds above its rent-exempt minimum is
+/// sent to the referrer.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    msg!("claiming referral rewards");
+
+    let balance_info = ctx.accounts.referrer_balance.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(balance_info.data_len());
+    let amount = balance_info.lamports().saturating_sub(rent_minimum);
+    require!(amount > 0, MyError::NoReferralRewards);
+
+    // The balance account is owned by the program, so lamports can be moved directly
+    **balance_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.referrer.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let referrer_balance = &mut ctx.accounts.referrer_balance;
+    referrer_balance.total_claimed = referrer_balance.total_claimed
+        .checked_add(amount)
+        .ok_or(MyError::MathOverflow)?;
+
+    emit_cpi!(ReferralRewardsClaimed {
+        lottery: referrer_balance.lottery,
+        referrer: referrer_balance.referrer,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::buyer_account::BuyerRecord;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::BuyerRecordClosed;
+use crate::BUYER_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBuyerRecord<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [BUYER_SEED, buyer_record.lottery.as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    /// CHECK: The lottery of the record, deserialized in the instruction as it may already be closed
+    #[account(address = buyer_record.lottery)]
+    pub lottery: UncheckedAccount<'info>,
+}
+
+/// Closes a buyer's ticket counter and returns its rent to the buyer
+///
+/// Only possible once the lottery is settled or cancelled, or after it was closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn close_buyer_record(ctx: Context<CloseBuyerRecord>) -> Result<()> {
+    msg!("closing buyer record");
+
+    if !ctx.accounts.lottery.data_is_empty() {
+        require_keys_eq!(*ctx.accounts.lottery.owner, *ctx.program_id, MyError::InvalidProgramAccount);
+        let data = ctx.accounts.lottery.try_borrow_data()?;
+        let lottery = LotteryStateData::try_deserialize(&mut &data[..])?;
+        require!(lottery.is_finished(), MyError::LotteryNotFinished);
+    }
+
+    emit_cpi!(BuyerRecordClosed {
+        lottery: ctx.accounts.buyer_record.lottery,
+        buyer: ctx.accounts.buyer.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::LotteryClosed;
+use crate::utils::yield_source::YieldSource;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, VAULT_SEED, YIELD_VAULT_SEED};
+use anchor_spl::token::{burn, close_account, Burn, CloseAccount, Mint, Token, TokenAccount};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLottery<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = lottery.is_finished() @ MyError::LotteryNotFinished
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    /// CHECK: Checked against the yield vault in the instruction
+    pub yield_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against the yield vault in the instruction
+    #[account(mut)]
+    pub position: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The protocol treasury, receiving the yield left over by no-loss lotteries
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        address = lottery.collection_mint @ MyError::InvalidCollection
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = collection_mint,
+        associated_token::authority = central_authority
+    )]
+    pub associated_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the reclaimed rent
+    #[account(
+        mut,
+        constraint = rent_payer.key() == lottery.rent_payer @ MyError::InvalidRentPayer
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes a settled or cancelled lottery and returns its rent to the original payer
+///
+/// The vault, the collection token account (after burning the collection token) and
+/// the lottery state account are closed, and their lamports go to the payer of
+/// `create_lottery`. Whatever the vault holds beyond its rent was sent to it after
+/// the funds were settled and goes to the payer too, so stray transfers can't keep
+/// the lottery open. A cancelled lottery whose tickets are still live owes their
+/// refunds, so its vault must hold nothing but its rent-exempt minimum.
+///
+/// No-loss lotteries can only be closed once every ticket principal was refunded and
+/// withdrawn from the yield source. The yield accrued since the last harvest is then
+/// withdrawn from the position and, with any harvested yield left in the vault, sent
+/// to the protocol treasury as `settle_lottery` does. The yield vault is closed too.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn close_lottery(ctx: Context<CloseLottery>) -> Result<()> {
+    msg!("closing lottery");
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let rent = Rent::get()?.minimum_balance(0);
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    // Empty the yield source position and hand the leftover yield to the treasury
+    if ctx.accounts.lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_ref()
+            .ok_or(MyError::MissingYieldVault)?;
+        require_eq!(yield_vault.principal, 0, MyError::PrincipalOutstanding);
+        require_eq!(yield_vault.deposited, 0, MyError::PrincipalOutstanding);
+
+        let yield_program = ctx.accounts.yield_program.as_ref()
+            .ok_or(MyError::InvalidYieldProgram)?;
+        require_keys_eq!(yield_program.key(), yield_vault.yield_program, MyError::InvalidYieldProgram);
+        let position = ctx.accounts.position.as_ref()
+            .ok_or(MyError::InvalidYieldPosition)?;
+        require_keys_eq!(position.key(), yield_vault.position, MyError::InvalidYieldPosition);
+        let treasury = ctx.accounts.treasury.as_ref()
+            .ok_or(MyError::InvalidTreasury)?;
+        require_keys_eq!(treasury.key(), ctx.accounts.central_authority.protocol_treasury, MyError::InvalidTreasury);
+        require_keys_neq!(treasury.key(), Pubkey::default(), MyError::TreasuryNotSet);
+
+        let yield_program = yield_program.to_account_info();
+        let position = position.to_account_info();
+        let vault = ctx.accounts.vault.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let yield_source = YieldSource {
+            yield_program: &yield_program,
+            position: &position,
+            owner: &vault,
+            system_program: &system_program,
+        };
+
+        let late_yield = yield_source.position_value()?;
+        if late_yield > 0 {
+            yield_source.withdraw(late_yield, signer_seeds)?;
+        }
+
+        let leftover = vault.lamports().saturating_sub(rent);
+        if leftover > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    system_program,
+                    Transfer {
+                        from: vault,
+                        to: treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                leftover,
+            )?;
+        }
+    }
+
+    // Live tickets of a cancelled lottery are still refunded out of the vault
+    let vault_balance = ctx.accounts.vault.lamports();
+    let lottery = &ctx.accounts.lottery;
+    if lottery.status == LotteryStatus::Cancelled && !lottery.no_loss && lottery.collection_size > 0 {
+        require!(vault_balance <= rent, MyError::VaultNotEmpty);
+    }
+
+    // Drain the vault back to the payer, with any lamports sent to it after the funds were paid out
+    if vault_balance > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.rent_payer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            vault_balance,
+        )?;
+    }
+
+    // Burn the collection token and close its account
+    let bump_seed = [ctx.bumps.central_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    if ctx.accounts.associated_token_account.amount > 0 {
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    from: ctx.accounts.associated_token_account.to_account_info(),
+                    authority: ctx.accounts.central_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.associated_token_account.amount,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.associated_token_account.to_account_info(),
+            destination: ctx.accounts.rent_payer.to_account_info(),
+            authority: ctx.accounts.central_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit_cpi!(LotteryClosed {
+        lottery: lottery_key,
+        rent_payer: ctx.accounts.rent_payer.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::promo_account::PromoRedemption;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::PromoRedemptionClosed;
+use crate::PROMO_CODE_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePromoRedemption<'info> {
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = redeemer,
+        has_one = redeemer,
+        seeds = [PROMO_CODE_SEED, promo_redemption.campaign.as_ref(), &promo_redemption.code_hash],
+        bump
+    )]
+    pub promo_redemption: Account<'info, PromoRedemption>,
+
+    /// CHECK: The lottery of the redemption, which must already be closed
+    #[account(address = promo_redemption.lottery)]
+    pub lottery: UncheckedAccount<'info>,
+}
+
+/// Closes a promo code redemption and returns its rent to the redeemer
+///
+/// The redemption is what keeps a code from being redeemed twice, so it can only
+/// be closed after the lottery account was closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn close_promo_redemption(ctx: Context<ClosePromoRedemption>) -> Result<()> {
+    msg!("closing promo redemption");
+
+    require!(ctx.accounts.lottery.data_is_empty(), MyError::LotteryNotClosed);
+
+    emit_cpi!(PromoRedemptionClosed {
+        lottery: ctx.accounts.promo_redemption.lottery,
+        campaign: ctx.accounts.promo_redemption.campaign,
+        redeemer: ctx.accounts.redeemer.key(),
+    });
+
+    Ok(())
+}
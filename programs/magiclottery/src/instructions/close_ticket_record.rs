@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketRecordClosed;
+use crate::{PICKS_SEED, TICKET_SEED, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseTicketRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [TICKET_SEED, ticket_record.lottery.as_ref(), &ticket_record.serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PICKS_SEED, ticket_record.lottery.as_ref(), &ticket_record.serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Option<Account<'info, TicketPicks>>,
+
+    /// CHECK: The lottery of the record, which must already be closed
+    #[account(address = ticket_record.lottery)]
+    pub lottery: UncheckedAccount<'info>,
+
+    /// CHECK: The yield vault of the lottery, deserialized in the instruction as it only exists in no-loss mode
+    #[account(
+        seeds = [YIELD_VAULT_SEED, ticket_record.lottery.as_ref()],
+        bump
+    )]
+    pub yield_vault: UncheckedAccount<'info>,
+}
+
+/// Closes the record of a ticket and returns its rent to the payer
+///
+/// Records are closed when their ticket is dissolved or claimed. A live ticket still
+/// needs its record to be dissolved or to claim a prize, even once the lottery is
+/// settled, so the others can only be closed after the lottery account was closed.
+/// In no-loss mode the record is what refunds the ticket principal, so it can't be
+/// closed while any principal is still owed. The picks of pick mode tickets are
+/// closed along with their record.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn close_ticket_record(ctx: Context<CloseTicketRecord>) -> Result<()> {
+    msg!("closing ticket record");
+
+    require!(ctx.accounts.lottery.data_is_empty(), MyError::LotteryNotClosed);
+
+    if !ctx.accounts.yield_vault.data_is_empty() {
+        require_keys_eq!(*ctx.accounts.yield_vault.owner, *ctx.program_id, MyError::InvalidProgramAccount);
+        let data = ctx.accounts.yield_vault.try_borrow_data()?;
+        let yield_vault = YieldVault::try_deserialize(&mut &data[..])?;
+        require_eq!(yield_vault.principal, 0, MyError::PrincipalOutstanding);
+    }
+
+    emit_cpi!(TicketRecordClosed {
+        lottery: ctx.accounts.ticket_record.lottery,
+        payer: ctx.accounts.payer.key(),
+        serial: ctx.accounts.ticket_record.serial,
+    });
+
+    Ok(())
+}
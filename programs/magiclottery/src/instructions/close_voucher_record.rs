@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::voucher_account::VoucherRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::VoucherRecordClosed;
+use crate::VOUCHER_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVoucherRecord<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        has_one = buyer,
+        seeds = [VOUCHER_SEED, voucher_record.lottery.as_ref(), buyer.key().as_ref(), &voucher_record.nonce.to_le_bytes()],
+        bump
+    )]
+    pub voucher_record: Account<'info, VoucherRecord>,
+
+    /// CHECK: The lottery of the record, which must already be closed
+    #[account(address = voucher_record.lottery)]
+    pub lottery: UncheckedAccount<'info>,
+}
+
+/// Closes a voucher redemption counter and returns its rent to the buyer
+///
+/// The record is what keeps a voucher from being redeemed more than its quantity,
+/// so it can only be closed after the lottery account was closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn close_voucher_record(ctx: Context<CloseVoucherRecord>) -> Result<()> {
+    msg!("closing voucher record");
+
+    require!(ctx.accounts.lottery.data_is_empty(), MyError::LotteryNotClosed);
+
+    emit_cpi!(VoucherRecordClosed {
+        lottery: ctx.accounts.voucher_record.lottery,
+        buyer: ctx.accounts.buyer.key(),
+        nonce: ctx.accounts.voucher_record.nonce,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::DrawCommitted;
+use crate::{CENTRAL_AUTHORITY_SEED, DRAW_SLOT_DELAY, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitDraw<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::LotteryNotOpen
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Closes ticket sales and commits to the draw randomness
+///
+/// The commitment is the keccak hash of a secret that is revealed in `reveal_draw`.
+/// The draw seed mixes the secret with the hash of the draw slot, `DRAW_SLOT_DELAY`
+/// slots after the commitment, which nobody knows when the secret is fixed. The
+/// operator can still withhold an unfavourable reveal, so a draw that isn't revealed
+/// within `REVEAL_WINDOW_SLOTS` of the draw slot lets anyone cancel the lottery.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `commitment` - The keccak hash of the draw secret
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn commit_draw(ctx: Context<CommitDraw>, commitment: [u8; 32]) -> Result<()> {
+    msg!("committing lottery draw");
+
+    let lottery = &mut ctx.accounts.lottery;
+    require!(lottery.tickets_minted > 0, MyError::NoTickets);
+
+    lottery.draw_commitment = commitment;
+    lottery.draw_slot = Clock::get()?.slot + DRAW_SLOT_DELAY;
+    lottery.status = LotteryStatus::DrawCommitted;
+
+    emit_cpi!(DrawCommitted {
+        lottery: lottery.key(),
+        commitment,
+        draw_slot: lottery.draw_slot,
+    });
+
+    Ok(())
+}
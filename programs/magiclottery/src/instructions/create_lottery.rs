@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus, PickConfig, RefundPolicy, RoyaltyConfig, MAX_BASE_URI_LENGTH, MAX_PICKS};
 use crate::errors::errors::MyError;
-use crate::CENTRAL_AUTHORITY_SEED;
+use crate::events::lottery_events::LotteryCreated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
@@ -12,7 +14,9 @@ use anchor_spl::{
     token::{Mint, mint_to, MintTo, Token, TokenAccount},
 };
 use mpl_token_metadata::accounts::{ MasterEdition, Metadata as MetadataAccount };
+use mpl_token_metadata::MAX_SYMBOL_LENGTH;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateLottery<'info> {
     #[account(mut, signer)]
@@ -40,6 +44,15 @@ pub struct CreateLottery<'info> {
     )]
     pub collection_mint: Account<'info, Mint>,
 
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LotteryStateData::MAX_SIZE,
+        seeds = [LOTTERY_SEED, collection_mint.key().as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
     #[account(
         init_if_needed,
         payer = payer,
@@ -73,7 +86,8 @@ pub struct CreateLottery<'info> {
 ///
 /// This function initializes a new lottery by creating an NFT collection.
 /// It mints a single NFT, creates metadata and master edition accounts,
-/// and sets up the collection for future ticket minting.
+/// and sets up the collection for future ticket minting. The lottery state
+/// account keyed by the collection mint is initialized alongside it.
 ///
 /// # Arguments
 ///
@@ -81,17 +95,49 @@ pub struct CreateLottery<'info> {
 /// * `name` - The name of the lottery collection
 /// * `symbol` - The symbol for the lottery collection
 /// * `uri` - The URI for the lottery collection metadata
+/// * `base_uri` - The base URI of the ticket metadata, tickets resolve to `<base_uri>/<serial>.json`
+/// * `requires_cosign` - Whether ticket purchases must be co-signed by the authorizer wallet
+/// * `pick_config` - The pick-6 style configuration, or `None` for a single-winner raffle
+/// * `royalty_config` - The royalty fee and creator shares applied to every ticket
 ///
 /// # Returns
 ///
 /// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+#[allow(clippy::too_many_arguments)]
 pub fn create_lottery<'info>(
         ctx: Context<'_, '_, '_, 'info, CreateLottery<'info>>,
         name: String,
         symbol: String,
         uri: String,
+        base_uri: String,
+        requires_cosign: bool,
+        pick_config: Option<PickConfig>,
+        royalty_config: RoyaltyConfig,
 ) -> Result<()> {
         msg!("initializing collection");
+
+        // Ticket names and URIs are derived from these, so they must fit the metadata limits
+        require!(symbol.len() <= MAX_SYMBOL_LENGTH, MyError::SymbolTooLong);
+        require!(base_uri.len() <= MAX_BASE_URI_LENGTH, MyError::BaseUriTooLong);
+        let ticket_symbol = symbol.clone();
+
+        if let Some(pick_config) = &pick_config {
+            require!(
+                pick_config.pick_count > 0
+                    && pick_config.pick_count as usize <= MAX_PICKS
+                    && pick_config.pick_count <= pick_config.pick_max
+                    && pick_config.tier_payout_bps.len() == pick_config.pick_count as usize + 1
+                    && pick_config.tier_payout_bps[0] == 0
+                    && pick_config.tier_payout_bps.iter().map(|bps| *bps as u32).sum::<u32>() <= 10_000,
+                MyError::InvalidPickConfig
+            );
+        }
+
+        require!(royalty_config.is_valid(), MyError::InvalidRoyaltyConfig);
+        require!(
+            royalty_config.seller_fee_basis_points as u32 + ctx.accounts.central_authority.protocol_fee_bps as u32 <= 10_000,
+            MyError::ResaleFeesTooHigh
+        );
         
         // Prepare the signer seeds for CPI calls
         let bump_seed = [ctx.bumps.central_authority];
@@ -143,7 +189,8 @@ pub fn create_lottery<'info>(
             data_v2,
             true,
             true,
-            Some(CollectionDetails::V1 { size: 1 }),
+            // Bubblegum keeps the size in step as tickets are verified and unverified
+            Some(CollectionDetails::V1 { size: 0 }),
         )?;
 
         // Create master edition account
@@ -165,7 +212,42 @@ pub fn create_lottery<'info>(
 
         create_master_edition_v3(cpi_context, Some(0))?;
 
-        // Store the collection address in the central authority
-        //ctx.accounts.central_authority.collection_address = ctx.accounts.mint.key();
+        // Store the collection address in the lottery state
+        let lottery = &mut ctx.accounts.lottery;
+        lottery.collection_mint = ctx.accounts.collection_mint.key();
+        lottery.rent_payer = ctx.accounts.payer.key();
+        lottery.paused = false;
+        lottery.requires_cosign = requires_cosign;
+        lottery.allowlist_root = None;
+        lottery.symbol = ticket_symbol;
+        lottery.base_uri = base_uri;
+        lottery.tickets_minted = 0;
+        lottery.collection_size = 0;
+        lottery.seller_fee_basis_points = royalty_config.seller_fee_basis_points;
+        lottery.creators = royalty_config.creators;
+        lottery.transfer_lock_starts_at = 0;
+        lottery.transfer_lock_ends_at = 0;
+        lottery.refund_policy = RefundPolicy::Holder;
+        lottery.referral_bps = 0;
+        lottery.pricing = None;
+        lottery.discount_collections = vec![];
+        lottery.no_loss = false;
+        lottery.draw_slot = 0;
+        lottery.tier_winners = vec![];
+        lottery.claims_open_at = 0;
+        lottery.claim_deadline = 0;
+        lottery.status = LotteryStatus::Open;
+        if let Some(pick_config) = pick_config {
+            lottery.pick_count = pick_config.pick_count;
+            lottery.pick_max = pick_config.pick_max;
+            lottery.tier_payout_bps = pick_config.tier_payout_bps;
+            lottery.tier_winners = vec![0; pick_config.pick_count as usize + 1];
+        }
+
+        emit_cpi!(LotteryCreated {
+            lottery: lottery.key(),
+            collection_mint: lottery.collection_mint,
+            requires_cosign,
+        });
         Ok(())
     }
\ No newline at end of file
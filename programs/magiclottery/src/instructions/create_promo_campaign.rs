@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::promo_account::PromoCampaign;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::PromoCampaignCreated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PROMO_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct CreatePromoCampaign<'info> {
+    #[account(mut, signer)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PromoCampaign::MAX_SIZE,
+        seeds = [PROMO_SEED, lottery.key().as_ref(), &campaign_id.to_le_bytes()],
+        bump
+    )]
+    pub promo_campaign: Account<'info, PromoCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a promo campaign giving away free tickets
+///
+/// Each code can be redeemed once with `redeem_promo`, until the campaign
+/// budget is used up.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `campaign_id` - The identifier of the campaign within the lottery
+/// * `codes_root` - The Merkle root of the `keccak(redeemer || code)` leaves binding each promo code to its redeemer, see `promo_leaf`
+/// * `budget` - The maximum number of tickets the campaign gives away
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn create_promo_campaign(
+    ctx: Context<CreatePromoCampaign>,
+    campaign_id: u64,
+    codes_root: [u8; 32],
+    budget: u32,
+) -> Result<()> {
+    msg!("creating promo campaign");
+
+    let promo_campaign = &mut ctx.accounts.promo_campaign;
+    promo_campaign.lottery = ctx.accounts.lottery.key();
+    promo_campaign.campaign_id = campaign_id;
+    promo_campaign.codes_root = codes_root;
+    promo_campaign.budget = budget;
+    promo_campaign.redeemed = 0;
+
+    emit_cpi!(PromoCampaignCreated {
+        lottery: promo_campaign.lottery,
+        campaign: promo_campaign.key(),
+        campaign_id,
+        budget,
+    });
+    Ok(())
+}
@@ -1,17 +1,22 @@
 use anchor_lang::prelude::*;
 use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, TreeRegistration, MAX_TREES};
 use crate::errors::errors::MyError;
+use crate::events::lottery_events::TreeCreated;
 use crate::clones::program_ids::*;
-use crate::CENTRAL_AUTHORITY_SEED;
+use crate::utils::tree::{is_supported_tree, merkle_tree_account_size};
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
 
 use mpl_bubblegum::instructions::CreateTreeConfigCpiBuilder;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateLotteryTree<'info> {
     #[account(mut, signer)]
     pub payer: Signer<'info>,
 
     #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet,
         constraint = payer.key() != authorizer_wallet.key() @ MyError::PayerMustBeClient
     )]
     pub authorizer_wallet: Signer<'info>,
@@ -23,6 +28,13 @@ pub struct CreateLotteryTree<'info> {
     )]
     pub central_authority: Account<'info, CentralStateData>,
 
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
     /// CHECK: This account must be all zeros
     #[account(
     zero,
@@ -44,14 +56,17 @@ pub struct CreateLotteryTree<'info> {
 /// Creates a new lottery tree (merkle tree) for storing compressed NFT tickets
 ///
 /// This function initializes a new merkle tree using the Bubblegum program.
-/// It sets up the tree configuration and stores the merkle tree address in the central authority.
+/// It sets up the tree configuration and appends the merkle tree to the lottery's tree list,
+/// which `buy_ticket` fills in order. The dimensions must be a pair supported by
+/// SPL Account Compression and match the size the merkle tree account was allocated with,
+/// including the canopy. A deeper canopy keeps burn and claim proofs short.
 ///
 /// # Arguments
 ///
 /// * `ctx` - The context struct containing the accounts required for the operation
 /// * `max_depth` - The maximum depth of the merkle tree
 /// * `max_buffer_size` - The maximum buffer size for the merkle tree
-/// * `required_tree_account_size` - The required size of the tree account in bytes
+/// * `canopy_depth` - The number of tree levels cached on-chain below the root
 ///
 /// # Returns
 ///
@@ -60,9 +75,22 @@ pub fn create_lottery_tree<'info>(
         ctx: Context<'_, '_, '_, 'info, CreateLotteryTree<'info>>,
         max_depth: u32,
         max_buffer_size: u32,
+        canopy_depth: u32,
 ) -> Result<()> {
         msg!("initializing collection merkle tree");
 
+        let lottery = &ctx.accounts.lottery;
+        require!(lottery.trees.len() < MAX_TREES, MyError::TooManyTrees);
+
+        // Reject dimensions the compression program can't handle before paying for the CPI
+        require!(is_supported_tree(max_depth, max_buffer_size), MyError::UnsupportedTreeAccountSize);
+        require!(canopy_depth < max_depth, MyError::InvalidCanopyDepth);
+        require_eq!(
+            ctx.accounts.merkle_tree.data_len(),
+            merkle_tree_account_size(max_depth, max_buffer_size, canopy_depth),
+            MyError::UnsupportedTreeAccountSize
+        );
+
         // Prepare the signer seeds for the CPI call
         let bump_seed = [ctx.bumps.central_authority];
         let signer_seeds: &[&[&[u8]]] = &[&[
@@ -88,8 +116,22 @@ pub fn create_lottery_tree<'info>(
         
         
 
-        // Remove the line that stores the merkle tree address in the central authority
-        // ctx.accounts.central_authority.merkle_tree_address = Some(ctx.accounts.merkle_tree.key());
+        // Record the tree and its dimensions on the lottery
+        ctx.accounts.lottery.trees.push(TreeRegistration {
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            max_depth,
+            max_buffer_size,
+            canopy_depth,
+            tree_delegate: None,
+        });
+
+        emit_cpi!(TreeCreated {
+            lottery: ctx.accounts.lottery.key(),
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            max_depth,
+            max_buffer_size,
+            canopy_depth,
+        });
         
         Ok(())
     }
\ No newline at end of file
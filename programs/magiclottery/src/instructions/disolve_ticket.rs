@@ -1,11 +1,22 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
 use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, RefundPolicy};
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::global_accounts::yield_vault_account::YieldVault;
 use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketDissolved;
 use crate::clones::program_ids::*;
-use mpl_bubblegum::instructions::BurnCpiBuilder;
-use crate::CENTRAL_AUTHORITY_SEED;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::hash::hash_metadata;
+use mpl_bubblegum::instructions::{BurnCpiBuilder, UnverifyCollectionCpiBuilder};
+use mpl_token_metadata::accounts::{ MasterEdition, Metadata as MetadataAccount };
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PICKS_SEED, TICKET_SEED, VAULT_SEED, YIELD_VAULT_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(serial: u64)]
 pub struct DisolveTicket<'info> {
     #[account(mut, signer)]
     pub payer: Signer<'info>,
@@ -18,19 +29,91 @@ pub struct DisolveTicket<'info> {
 
     #[account(
         seeds = [CENTRAL_AUTHORITY_SEED],
-        bump
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
     )]
     pub central_authority: Account<'info, CentralStateData>,
-    /// CHECK: This account is checked in the instruction
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.can_dissolve() @ MyError::LotteryNotOpen
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    #[account(
+        mut,
+        close = ticket_payer,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    #[account(
+        mut,
+        close = ticket_payer,
+        seeds = [PICKS_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Option<Account<'info, TicketPicks>>,
+
+    /// CHECK: The wallet that paid for the ticket, receiving the record rent
+    #[account(
+        mut,
+        address = ticket_record.payer
+    )]
+    pub ticket_payer: UncheckedAccount<'info>,
+
+    /// CHECK: The owner of the ticket, checked against the leaf by Bubblegum
     #[account(mut)]
     pub leaf_owner: Signer<'info>,
+    /// CHECK: The current leaf delegate, checked against the leaf by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
     /// CHECK: This account is modified in the downstream program
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
     pub merkle_tree: UncheckedAccount<'info>,
     /// CHECK: This account is checked in the instruction
     pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = lottery.collection_mint)]
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = MetadataAccount::find_pda(&lottery.collection_mint).0,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = MasterEdition::find_pda(&lottery.collection_mint).0)]
+    pub edition_account: UncheckedAccount<'info>,
+
+    /// CHECK: This is just used as a signing PDA.
+    pub bubblegum_signer: UncheckedAccount<'info>,
     pub log_wrapper: Program<'info, Noop>,
     pub compression_program: Program<'info, SplAccountCompression>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
     pub bubblegum_program: Program<'info, MplBubblegum>,
     pub system_program: Program<'info, System>,
 }
@@ -41,13 +124,23 @@ pub struct DisolveTicket<'info> {
 ///
 /// This function burns a compressed NFT ticket using the Bubblegum program.
 /// It verifies the merkle tree and other constraints before proceeding with the burn operation.
+/// Bubblegum burns leave the sized collection counter untouched, so the ticket is
+/// first unverified from the collection, which decrements it. Tickets can't be
+/// dissolved during the transfer lock window; outside it any leaf owner can
+/// dissolve them.
+///
+/// The ticket price is refunded from the vault to the holder or to the payer,
+/// following the refund policy recorded at purchase, and the ticket record is closed.
+/// In pick mode the ticket picks are closed too, both returning their rent to the
+/// ticket payer.
+/// No-loss lottery tickets can also be dissolved after the draw, and their refund
+/// is taken out of the principal held in the vault.
 ///
 /// # Arguments
 ///
 /// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
 /// * `root` - The root hash of the merkle tree
-/// * `data_hash` - The hash of the NFT's data
-/// * `creator_hash` - The hash of the NFT's creator
 /// * `nonce` - A unique number used to prevent replay attacks
 /// * `index` - The index of the leaf in the merkle tree
 ///
@@ -56,19 +149,62 @@ pub struct DisolveTicket<'info> {
 /// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
 pub fn disolve_ticket<'info>(
     ctx: Context<'_, '_, '_, 'info, DisolveTicket<'info>>,
+    serial: u64,
     root: [u8; 32],
-    data_hash: [u8; 32],
-    creator_hash: [u8; 32],
     nonce: u64,
     index: u32
 ) -> Result<()> {
-    msg!("buying ticket from collection");
+    msg!("dissolving ticket from collection");
     
-    // Prepare the remaining accounts for the CPI call
-    let remaining_accounts: Vec<(&AccountInfo, bool, bool)> = ctx.remaining_accounts
-        .iter()
-        .map(|account| (account, account.is_signer, account.is_writable))
-        .collect();
+    let lottery = &ctx.accounts.lottery;
+    require!(!lottery.is_pick_mode() || ctx.accounts.ticket_picks.is_some(), MyError::MissingTicketPicks);
+    require!(!lottery.is_transfer_locked(Clock::get()?.unix_timestamp), MyError::TicketsLocked);
+
+    // Rebuild the ticket leaf from its serial
+    let (leaf, _) = ticket_leaf(lottery, &ctx.accounts.merkle_tree.key(), serial, ctx.remaining_accounts, 0)?;
+    let mut metadata = leaf.metadata;
+
+    // Prepare the signer seeds for the CPI call
+    let bump_seed = [ctx.bumps.central_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    // Unverify the ticket first so Bubblegum decrements the collection size
+    UnverifyCollectionCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.leaf_owner.to_account_info())
+        .leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .payer(&ctx.accounts.payer.to_account_info())
+        .tree_creator_or_delegate(&ctx.accounts.central_authority.to_account_info())
+        .collection_authority(&ctx.accounts.central_authority.to_account_info())
+        .collection_authority_record_pda(Some(&ctx.accounts.bubblegum_program.to_account_info()))
+        .collection_mint(&ctx.accounts.collection_mint.to_account_info())
+        .collection_metadata(&ctx.accounts.collection_metadata.to_account_info())
+        .collection_edition(&ctx.accounts.edition_account.to_account_info())
+        .bubblegum_signer(&ctx.accounts.bubblegum_signer.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .token_metadata_program(&ctx.accounts.token_metadata_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .metadata(metadata.clone())
+        .invoke_signed(signer_seeds)?;
+
+    // The proof stays valid against the same root, only the leaf data hash changed
+    if let Some(collection) = metadata.collection.as_mut() {
+        collection.verified = false;
+    }
+    let data_hash = hash_metadata(&metadata)?;
 
     // Build and invoke the Burn CPI to the Bubblegum program
     BurnCpiBuilder::new(
@@ -76,18 +212,68 @@ pub fn disolve_ticket<'info>(
     )
         .tree_config(&ctx.accounts.tree_config.to_account_info())
         .leaf_owner(&ctx.accounts.leaf_owner.to_account_info(), true)
-        .leaf_delegate(&ctx.accounts.leaf_owner.to_account_info(), true)
+        .leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info(), false)
         .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
         .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
         .compression_program(&ctx.accounts.compression_program.to_account_info())
         .system_program(&ctx.accounts.system_program.to_account_info())
-        .add_remaining_accounts(&remaining_accounts)
+        .add_remaining_accounts(&leaf.proof)
         .root(root)
         .data_hash(data_hash)
-        .creator_hash(creator_hash)
+        .creator_hash(leaf.creator_hash)
         .nonce(nonce)
         .index(index)
         .invoke()?;
 
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.collection_size = lottery.collection_size.saturating_sub(1);
+
+    // Refund the ticket price out of the vault
+    let ticket_record = &ctx.accounts.ticket_record;
+    let refund_receiver = match ticket_record.refund_policy {
+        RefundPolicy::Holder => ctx.accounts.leaf_owner.to_account_info(),
+        RefundPolicy::Payer => ctx.accounts.ticket_payer.to_account_info(),
+    };
+    let refund = ticket_record.price;
+
+    if lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_mut()
+            .ok_or(MyError::MissingYieldVault)?;
+        require!(refund <= yield_vault.liquid_principal(), MyError::InsufficientLiquidity);
+        yield_vault.principal -= refund;
+    }
+
+    let lottery_key = lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    if refund > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: refund_receiver.clone(),
+                },
+                signer_seeds,
+            ),
+            refund,
+        )?;
+    }
+
+    emit_cpi!(TicketDissolved {
+        lottery: lottery.key(),
+        leaf_owner: ctx.accounts.leaf_owner.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        serial,
+        leaf_index: index,
+        refund_receiver: refund_receiver.key(),
+        refund,
+    });
+
     Ok(())
-}
\ No newline at end of file
+}
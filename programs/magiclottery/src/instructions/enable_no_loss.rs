@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::NoLossEnabled;
+use crate::utils::yield_source::YieldSource;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, VAULT_SEED, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnableNoLoss<'info> {
+    #[account(mut, signer)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::NoLossUnavailable,
+        constraint = lottery.tickets_minted == 0 @ MyError::NoLossUnavailable,
+        constraint = lottery.referral_bps == 0 @ MyError::NoLossUnavailable
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds, owner of the yield source position
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + YieldVault::MAX_SIZE,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// CHECK: The yield source program, implementing the interface in `utils::yield_source`
+    #[account(constraint = yield_program.executable @ MyError::InvalidYieldProgram)]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// CHECK: The position opened by the yield program, checked in the instruction
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Turns a lottery into a no-loss lottery backed by a yield source
+///
+/// Ticket payments become principal, refunded in full when a ticket is dissolved,
+/// even after the draw, and winning tickets get their principal back with the prize.
+/// The principal can be deposited into the yield source and only the yield it earns
+/// funds the prizes. The yield source position is opened for the lottery vault.
+///
+/// It can only be enabled on an open lottery before its first ticket is sold, and
+/// referral rewards, which aren't refundable, must be disabled.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn enable_no_loss(ctx: Context<EnableNoLoss>) -> Result<()> {
+    msg!("enabling no-loss mode");
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    let yield_program = ctx.accounts.yield_program.to_account_info();
+    let position = ctx.accounts.position.to_account_info();
+    let vault = ctx.accounts.vault.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let yield_source = YieldSource {
+        yield_program: &yield_program,
+        position: &position,
+        owner: &vault,
+        system_program: &system_program,
+    };
+
+    yield_source.open_position(&ctx.accounts.payer.to_account_info(), signer_seeds)?;
+    require_eq!(yield_source.position_value()?, 0, MyError::InvalidYieldPosition);
+
+    let yield_vault = &mut ctx.accounts.yield_vault;
+    yield_vault.lottery = lottery_key;
+    yield_vault.yield_program = yield_program.key();
+    yield_vault.position = position.key();
+    yield_vault.principal = 0;
+    yield_vault.deposited = 0;
+    yield_vault.harvested_yield = 0;
+
+    ctx.accounts.lottery.no_loss = true;
+
+    emit_cpi!(NoLossEnabled {
+        lottery: lottery_key,
+        yield_program: yield_program.key(),
+        position: position.key(),
+    });
+
+    Ok(())
+}
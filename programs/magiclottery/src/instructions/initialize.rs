@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::global_accounts::central_account::CentralStateData;
 use crate::errors::errors::MyError;
+use crate::events::lottery_events::CentralAuthorityInitialized;
 use crate::CENTRAL_AUTHORITY_SEED;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeCentralAuthority<'info> {
     #[account(mut)]
@@ -23,8 +25,7 @@ pub struct InitializeCentralAuthority<'info> {
         constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key())
     )]
     pub program_data: Account<'info, ProgramData>,
-    /// CHECK: This is the program account
-    pub program: AccountInfo<'info>,
+    // The program account is added by `#[event_cpi]`
 }
 
 pub fn initialize_central_authority(ctx: Context<InitializeCentralAuthority>, authorizer_wallet: Pubkey) -> Result<()> {
@@ -50,7 +51,14 @@ pub fn initialize_central_authority(ctx: Context<InitializeCentralAuthority>, au
     // Initialize the central authority
     ctx.accounts.central_authority.authorizer_wallet = authorizer_wallet;
     ctx.accounts.central_authority.is_initialized = true;
+    ctx.accounts.central_authority.paused = false;
+    ctx.accounts.central_authority.protocol_fee_bps = 0;
+    ctx.accounts.central_authority.protocol_treasury = authorizer_wallet;
     // Initialize other fields as needed
 
+    emit_cpi!(CentralAuthorityInitialized {
+        authorizer_wallet,
+    });
+
     Ok(())
 }
\ No newline at end of file
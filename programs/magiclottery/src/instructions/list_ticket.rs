@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::listing_account::TicketListing;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketListed;
+use crate::clones::program_ids::*;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::instructions::DelegateCpiBuilder;
+use crate::{CENTRAL_AUTHORITY_SEED, LISTING_SEED, LOTTERY_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(serial: u64)]
+pub struct ListTicket<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::LotteryNotOpen
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + TicketListing::MAX_SIZE,
+        seeds = [LISTING_SEED, lottery.key().as_ref(), seller.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub listing: Account<'info, TicketListing>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    /// CHECK: The current leaf delegate, checked against the leaf by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: This account is modified in the downstream program
+    #[account(
+        mut,
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub tree_config: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Lists a ticket for sale before the draw
+///
+/// The seller delegates the ticket leaf to the listing PDA, which lets
+/// `buy_listed_ticket` transfer it to a buyer paying the asking price. Listing is
+/// only possible outside the transfer lock window, where the leaf owner checked by
+/// Bubblegum is who the ticket belongs to, so the seller is recorded as its holder.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
+/// * `price` - The asking price in lamports
+/// * `root` - The root hash of the merkle tree
+/// * `nonce` - The nonce of the leaf
+/// * `index` - The index of the leaf in the merkle tree
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn list_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, ListTicket<'info>>,
+    serial: u64,
+    price: u64,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    msg!("listing ticket");
+
+    let lottery = &ctx.accounts.lottery;
+    require!(price > 0, MyError::InvalidListingPrice);
+    require!(!lottery.is_transfer_locked(Clock::get()?.unix_timestamp), MyError::TicketsLocked);
+    require!(lottery.resale_fees_fit(ctx.accounts.central_authority.protocol_fee_bps), MyError::ResaleFeesTooHigh);
+
+    let (leaf, _) = ticket_leaf(lottery, &ctx.accounts.merkle_tree.key(), serial, ctx.remaining_accounts, 0)?;
+
+    // Delegate the ticket to the listing PDA so it can be transferred on sale
+    DelegateCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.seller.to_account_info())
+        .previous_leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info())
+        .new_leaf_delegate(&ctx.accounts.listing.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .invoke()?;
+
+    ctx.accounts.ticket_record.holder = ctx.accounts.seller.key();
+
+    let listing = &mut ctx.accounts.listing;
+    listing.lottery = ctx.accounts.lottery.key();
+    listing.seller = ctx.accounts.seller.key();
+    listing.serial = serial;
+    listing.merkle_tree = ctx.accounts.merkle_tree.key();
+    listing.price = price;
+
+    emit_cpi!(TicketListed {
+        lottery: listing.lottery,
+        seller: listing.seller,
+        serial,
+        price,
+    });
+
+    Ok(())
+}
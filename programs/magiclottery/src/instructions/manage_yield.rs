@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::{YieldDeposited, YieldHarvested, YieldWithdrawn};
+use crate::utils::yield_source::YieldSource;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, VAULT_SEED, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositToYield<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds, owner of the yield source position
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// CHECK: address
+    #[account(address = yield_vault.yield_program @ MyError::InvalidYieldProgram)]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(mut, address = yield_vault.position @ MyError::InvalidYieldPosition)]
+    pub position: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawFromYield<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds, owner of the yield source position
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// CHECK: address
+    #[account(address = yield_vault.yield_program @ MyError::InvalidYieldProgram)]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(mut, address = yield_vault.position @ MyError::InvalidYieldPosition)]
+    pub position: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct HarvestYield<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.is_finished() @ MyError::LotteryFinished
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds, owner of the yield source position
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// CHECK: address
+    #[account(address = yield_vault.yield_program @ MyError::InvalidYieldProgram)]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(mut, address = yield_vault.position @ MyError::InvalidYieldPosition)]
+    pub position: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits principal held in the lottery vault into the yield source
+///
+/// Only principal can be deposited: prize funds already in the vault stay there.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `amount` - The amount of principal to deposit, in lamports
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn deposit_to_yield(ctx: Context<DepositToYield>, amount: u64) -> Result<()> {
+    msg!("depositing principal into the yield source");
+    require!(amount <= ctx.accounts.yield_vault.liquid_principal(), MyError::InsufficientLiquidity);
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    YieldSource {
+        yield_program: &ctx.accounts.yield_program.to_account_info(),
+        position: &ctx.accounts.position.to_account_info(),
+        owner: &ctx.accounts.vault.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+        .deposit(amount, signer_seeds)?;
+
+    let yield_vault = &mut ctx.accounts.yield_vault;
+    yield_vault.deposited += amount;
+
+    emit_cpi!(YieldDeposited {
+        lottery: lottery_key,
+        amount,
+        deposited: yield_vault.deposited,
+    });
+
+    Ok(())
+}
+
+/// Withdraws principal from the yield source back to the lottery vault
+///
+/// Refunds are paid out of the vault, so principal must be withdrawn before tickets
+/// whose principal is deposited can be dissolved or claimed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `amount` - The amount of principal to withdraw, in lamports
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn withdraw_from_yield(ctx: Context<WithdrawFromYield>, amount: u64) -> Result<()> {
+    msg!("withdrawing principal from the yield source");
+    require!(amount <= ctx.accounts.yield_vault.deposited, MyError::InsufficientLiquidity);
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    YieldSource {
+        yield_program: &ctx.accounts.yield_program.to_account_info(),
+        position: &ctx.accounts.position.to_account_info(),
+        owner: &ctx.accounts.vault.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+        .withdraw(amount, signer_seeds)?;
+
+    let yield_vault = &mut ctx.accounts.yield_vault;
+    yield_vault.deposited -= amount;
+
+    emit_cpi!(YieldWithdrawn {
+        lottery: lottery_key,
+        amount,
+        deposited: yield_vault.deposited,
+    });
+
+    Ok(())
+}
+
+/// Withdraws the yield accrued on the deposited principal into the lottery vault
+///
+/// The yield is the position value above the deposited principal. Once in the vault
+/// it funds the prizes: the prize pool snapshotted by `reveal_draw` excludes principal.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn harvest_yield(ctx: Context<HarvestYield>) -> Result<()> {
+    msg!("harvesting yield");
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let bump_seed = [ctx.bumps.vault];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        lottery_key.as_ref(),
+        &bump_seed,
+    ]];
+
+    let yield_program = ctx.accounts.yield_program.to_account_info();
+    let position = ctx.accounts.position.to_account_info();
+    let vault = ctx.accounts.vault.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let yield_source = YieldSource {
+        yield_program: &yield_program,
+        position: &position,
+        owner: &vault,
+        system_program: &system_program,
+    };
+
+    let amount = yield_source.position_value()?
+        .saturating_sub(ctx.accounts.yield_vault.deposited);
+    require!(amount > 0, MyError::NoYield);
+    yield_source.withdraw(amount, signer_seeds)?;
+
+    let yield_vault = &mut ctx.accounts.yield_vault;
+    yield_vault.harvested_yield = yield_vault.harvested_yield
+        .checked_add(amount)
+        .ok_or(MyError::MathOverflow)?;
+
+    emit_cpi!(YieldHarvested {
+        lottery: lottery_key,
+        amount,
+        harvested_yield: yield_vault.harvested_yield,
+    });
+
+    Ok(())
+}
@@ -4,9 +4,69 @@ pub mod create_lottery;
 pub mod buy_ticket;
 pub mod disolve_ticket;
 pub mod create_tree;
+pub mod set_paused;
+pub mod set_allowlist;
+pub mod commit_draw;
+pub mod reveal_draw;
+pub mod claim_prize;
+pub mod set_tree_delegate;
+pub mod settle_lottery;
+pub mod cancel_lottery;
+pub mod close_lottery;
+pub mod close_buyer_record;
+pub mod update_lottery_metadata;
+pub mod set_transfer_lock;
+pub mod unlock_ticket;
+pub mod set_protocol_fee;
+pub mod list_ticket;
+pub mod buy_listed_ticket;
+pub mod cancel_listing;
+pub mod set_refund_policy;
+pub mod close_ticket_record;
+pub mod set_referral_bps;
+pub mod claim_referral_rewards;
+pub mod create_promo_campaign;
+pub mod redeem_promo;
+pub mod set_pricing;
+pub mod enable_no_loss;
+pub mod manage_yield;
+pub mod register_win;
+pub mod transfer_ticket;
+pub mod close_voucher_record;
+pub mod close_promo_redemption;
 // Re-export all public items from submodules
 pub use initialize::*;
 pub use create_lottery::*;
 pub use buy_ticket::*;
 pub use disolve_ticket::*;
 pub use create_tree::*;
+pub use set_paused::*;
+pub use set_allowlist::*;
+pub use commit_draw::*;
+pub use reveal_draw::*;
+pub use claim_prize::*;
+pub use set_tree_delegate::*;
+pub use settle_lottery::*;
+pub use cancel_lottery::*;
+pub use close_lottery::*;
+pub use close_buyer_record::*;
+pub use update_lottery_metadata::*;
+pub use set_transfer_lock::*;
+pub use unlock_ticket::*;
+pub use set_protocol_fee::*;
+pub use list_ticket::*;
+pub use buy_listed_ticket::*;
+pub use cancel_listing::*;
+pub use set_refund_policy::*;
+pub use close_ticket_record::*;
+pub use set_referral_bps::*;
+pub use claim_referral_rewards::*;
+pub use create_promo_campaign::*;
+pub use redeem_promo::*;
+pub use set_pricing::*;
+pub use enable_no_loss::*;
+pub use manage_yield::*;
+pub use register_win::*;
+pub use transfer_ticket::*;
+pub use close_voucher_record::*;
+pub use close_promo_redemption::*;
\ No newline at end of file
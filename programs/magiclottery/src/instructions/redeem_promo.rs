@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::buyer_account::BuyerRecord;
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::global_accounts::promo_account::{PromoCampaign, PromoRedemption};
+use crate::instructions::buy_ticket::TicketReceipt;
+use crate::utils::allowlist::{AllowlistProof, enforce_allowlist};
+use crate::utils::promo::{promo_code_hash, promo_leaf, verify_promo_code};
+use crate::utils::mint::{MintTicketAccounts, mint_ticket};
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::PromoRedeemed;
+use crate::clones::program_ids::*;
+use crate::{BUYER_SEED, CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PICKS_SEED, PROMO_CODE_SEED, PROMO_SEED, TICKET_LOCK_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RedeemPromo<'info> {
+    #[account(mut, signer)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, collection_mint.key().as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Open @ MyError::LotteryNotOpen,
+        constraint = !lottery.no_loss @ MyError::ZeroDeposit
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        has_one = lottery,
+        seeds = [PROMO_SEED, lottery.key().as_ref(), &promo_campaign.campaign_id.to_le_bytes()],
+        bump
+    )]
+    pub promo_campaign: Account<'info, PromoCampaign>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PromoRedemption::MAX_SIZE,
+        seeds = [PROMO_CODE_SEED, promo_campaign.key().as_ref(), &promo_code_hash(&code)],
+        bump
+    )]
+    pub promo_redemption: Account<'info, PromoRedemption>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TicketPicks::MAX_SIZE,
+        seeds = [PICKS_SEED, lottery.key().as_ref(), &(lottery.tickets_minted + 1).to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Option<Account<'info, TicketPicks>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TicketRecord::MAX_SIZE,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &(lottery.tickets_minted + 1).to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BuyerRecord::MAX_SIZE,
+        seeds = [BUYER_SEED, lottery.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// CHECK: This account is checked in the instruction
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: This account is neither written to nor read from.
+    pub leaf_owner: AccountInfo<'info>,
+
+    /// CHECK: The PDA set as leaf delegate until the ticket is unlocked, it never signs and restricts nothing
+    #[account(
+        seeds = [TICKET_LOCK_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub ticket_lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: unsafe
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// The tree delegate set with `set_tree_delegate`, required instead of the central authority once set
+    pub tree_delegate: Option<Signer<'info>>,
+
+    /// CHECK: This account is checked in the instruction
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK:
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked in the instruction
+    pub edition_account: UncheckedAccount<'info>,
+
+    /// CHECK: This is just used as a signing PDA.
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Redeems a promo code for a free ticket
+///
+/// The code must be part of the campaign Merkle root, whose leaves bind each code to
+/// the wallet it was issued to, so only that wallet can redeem it as `payer`. A code
+/// can only be redeemed once, as the redemption PDA keyed by its hash can't be created
+/// twice. The ticket is minted exactly like a bought one, without payment, so it has
+/// nothing to refund.
+///
+/// Redemptions go through the same gates as `buy_ticket`: the authorizer co-signature
+/// when the lottery requires it, and the allowlist with its per-address cap, which
+/// counts promo tickets along with bought ones.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `code` - The promo code
+/// * `proof` - The sibling hashes from the code leaf up to the campaign root
+/// * `allowlist_proof` - The allowlist proof, required when the lottery has an allowlist root
+/// * `picks` - The player's numbers in pick mode, empty for raffles
+///
+/// # Returns
+///
+/// Returns the `TicketReceipt` of the minted ticket if the redemption succeeds, or an error otherwise.
+pub fn redeem_promo<'info>(
+    ctx: Context<'_, '_, '_, 'info, RedeemPromo<'info>>,
+    code: String,
+    proof: Vec<[u8; 32]>,
+    allowlist_proof: Option<AllowlistProof>,
+    picks: Vec<u8>,
+) -> Result<TicketReceipt> {
+    msg!("redeeming promo code");
+
+    if ctx.accounts.lottery.requires_cosign {
+        require!(ctx.accounts.authorizer_wallet.is_some(), MyError::CosignRequired);
+    }
+
+    // Enforce the allowlist and its per-address cap
+    let lottery_key = ctx.accounts.lottery.key();
+    enforce_allowlist(
+        &ctx.accounts.lottery,
+        lottery_key,
+        ctx.accounts.payer.key(),
+        allowlist_proof,
+        ctx.accounts.buyer_record.as_deref_mut(),
+    )?;
+
+    let code_hash = promo_code_hash(&code);
+    let promo_campaign = &mut ctx.accounts.promo_campaign;
+    require!(
+        verify_promo_code(&promo_campaign.codes_root, &promo_leaf(&ctx.accounts.payer.key(), &code), &proof),
+        MyError::InvalidPromoCode
+    );
+    require!(promo_campaign.redeemed < promo_campaign.budget, MyError::PromoBudgetExhausted);
+    promo_campaign.redeemed += 1;
+
+    let promo_redemption = &mut ctx.accounts.promo_redemption;
+    promo_redemption.lottery = lottery_key;
+    promo_redemption.campaign = promo_campaign.key();
+    promo_redemption.code_hash = code_hash;
+    promo_redemption.redeemer = ctx.accounts.payer.key();
+
+    require!(ctx.accounts.lottery.validate_picks(&picks), MyError::InvalidPicks);
+
+    // Mint the ticket, promo tickets are free so they have nothing to refund
+    let accounts = MintTicketAccounts {
+        payer: ctx.accounts.payer.to_account_info(),
+        central_authority: ctx.accounts.central_authority.to_account_info(),
+        leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
+        ticket_lock: ctx.accounts.ticket_lock.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        tree_config: ctx.accounts.tree_config.to_account_info(),
+        tree_delegate: ctx.accounts.tree_delegate.as_ref().map(|tree_delegate| tree_delegate.to_account_info()),
+        collection_mint: ctx.accounts.collection_mint.to_account_info(),
+        collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+        edition_account: ctx.accounts.edition_account.to_account_info(),
+        bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let receipt = mint_ticket(
+        &mut ctx.accounts.lottery,
+        lottery_key,
+        accounts,
+        ctx.remaining_accounts,
+        ctx.bumps.central_authority,
+        ctx.accounts.ticket_picks.as_deref_mut(),
+        picks,
+        &mut ctx.accounts.ticket_record,
+        0,
+    )?;
+
+    emit_cpi!(PromoRedeemed {
+        lottery: lottery_key,
+        campaign: ctx.accounts.promo_campaign.key(),
+        leaf_owner: ctx.accounts.leaf_owner.key(),
+        serial: receipt.serial,
+        asset_id: receipt.asset_id,
+    });
+
+    Ok(receipt)
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::WinRegistered;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, PICKS_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(serial: u64)]
+pub struct RegisterWin<'info> {
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::Drawn @ MyError::LotteryNotDrawn
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        seeds = [PICKS_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_picks: Account<'info, TicketPicks>,
+
+    /// The record of the ticket, proving it wasn't dissolved
+    #[account(
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+}
+
+/// Registers a winning pick mode ticket for its prize tier
+///
+/// Each tier's share of the prize pool is split among the tickets registered for it,
+/// so winners register during the window after the reveal and claim once it closes.
+/// Picks are public, so anyone can register a winning ticket on behalf of its holder.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn register_win(ctx: Context<RegisterWin>, serial: u64) -> Result<()> {
+    msg!("registering winning ticket");
+
+    let lottery = &mut ctx.accounts.lottery;
+    require!(Clock::get()?.unix_timestamp < lottery.claims_open_at, MyError::WinRegistrationClosed);
+
+    let ticket_picks = &mut ctx.accounts.ticket_picks;
+    require!(!ticket_picks.registered, MyError::WinAlreadyRegistered);
+
+    let matches = lottery.count_matches(&ticket_picks.picks);
+    require!(lottery.tier_payout_bps[matches] > 0, MyError::NoPrize);
+
+    lottery.tier_winners[matches] += 1;
+    ticket_picks.registered = true;
+
+    emit_cpi!(WinRegistered {
+        lottery: lottery.key(),
+        serial,
+        matches: matches as u8,
+    });
+
+    Ok(())
+}
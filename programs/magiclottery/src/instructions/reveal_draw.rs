@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{keccak, sysvar};
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::WinnerSelected;
+use crate::utils::draw::{draw_seed, draw_winning_numbers, draw_winning_ticket, slot_hash_at};
+use crate::{CENTRAL_AUTHORITY_SEED, CLAIM_PERIOD, LOTTERY_SEED, TICKET_SEED, VAULT_SEED, WIN_REGISTRATION_PERIOD, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealDraw<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump,
+        constraint = !central_authority.paused @ MyError::ProgramPaused
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = !lottery.paused @ MyError::LotteryPaused,
+        constraint = lottery.status == LotteryStatus::DrawCommitted @ MyError::DrawNotCommitted
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds
+    #[account(
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    /// CHECK: The SlotHashes sysvar, read manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Reveals the draw secret and selects the winners
+///
+/// The draw seed mixes the secret with the hash of the draw slot recorded by
+/// `commit_draw`, so the reveal must wait until that slot has passed, and must happen
+/// within `REVEAL_WINDOW_SLOTS` of it. Raffles draw a single winning ticket serial;
+/// pick mode lotteries draw the winning numbers instead.
+///
+/// Raffles only draw among live tickets: the remaining accounts are the ticket record
+/// PDAs of the serials drawn by successive attempts, and the first serial whose record
+/// still exists wins. Dissolved tickets have no record, so their attempt is redrawn.
+/// Pick mode winners then have `WIN_REGISTRATION_PERIOD` to register before claims open,
+/// and prizes can be claimed for `CLAIM_PERIOD` before the lottery can be settled. The prize pool is snapshotted from the vault balance above its
+/// rent-exempt minimum, excluding the principal of no-loss lotteries.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `secret` - The secret whose keccak hash was committed in `commit_draw`
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn reveal_draw(ctx: Context<RevealDraw>, secret: [u8; 32]) -> Result<()> {
+    msg!("revealing lottery draw");
+
+    let lottery = &mut ctx.accounts.lottery;
+    require!(
+        keccak::hash(&secret).to_bytes() == lottery.draw_commitment,
+        MyError::InvalidDrawSecret
+    );
+
+    let slot = Clock::get()?.slot;
+    require!(slot > lottery.draw_slot, MyError::DrawSlotNotReached);
+    require!(!lottery.is_reveal_expired(slot), MyError::RevealExpired);
+
+    let slot_hash = slot_hash_at(&ctx.accounts.slot_hashes.to_account_info(), lottery.draw_slot)?;
+    let seed = draw_seed(&secret, &slot_hash, &lottery.key());
+
+    if lottery.is_pick_mode() {
+        lottery.winning_numbers = draw_winning_numbers(&seed, lottery.pick_count, lottery.pick_max);
+    } else {
+        require!(lottery.collection_size > 0, MyError::NoTickets);
+
+        let lottery_key = lottery.key();
+        let mut winning_ticket = None;
+        for (attempt, ticket_record) in ctx.remaining_accounts.iter().enumerate() {
+            let serial = draw_winning_ticket(&seed, attempt as u64, lottery.tickets_minted);
+            let (record_key, _) = Pubkey::find_program_address(
+                &[TICKET_SEED, lottery_key.as_ref(), &serial.to_le_bytes()],
+                ctx.program_id,
+            );
+            require_keys_eq!(ticket_record.key(), record_key, MyError::InvalidTicketRecord);
+
+            if ticket_record.owner == ctx.program_id && !ticket_record.data_is_empty() {
+                winning_ticket = Some(serial);
+                break;
+            }
+        }
+        lottery.winning_ticket = winning_ticket.ok_or(MyError::NoLiveTicketDrawn)?;
+    }
+
+    lottery.prize_pool = ctx.accounts.vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_ref()
+            .ok_or(MyError::MissingYieldVault)?;
+        lottery.prize_pool = yield_vault.prize_funds(lottery.prize_pool);
+    }
+    lottery.claims_open_at = Clock::get()?.unix_timestamp;
+    if lottery.is_pick_mode() {
+        lottery.claims_open_at += WIN_REGISTRATION_PERIOD;
+    }
+    lottery.claim_deadline = lottery.claims_open_at + CLAIM_PERIOD;
+    lottery.status = LotteryStatus::Drawn;
+
+    emit_cpi!(WinnerSelected {
+        lottery: lottery.key(),
+        winning_ticket: lottery.winning_ticket,
+        winning_numbers: lottery.winning_numbers.clone(),
+        prize_pool: lottery.prize_pool,
+    });
+
+    Ok(())
+}
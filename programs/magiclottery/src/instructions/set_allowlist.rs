@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::AllowlistUpdated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAllowlist<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Sets or clears the allowlist Merkle root of a lottery
+///
+/// When a root is set, `buy_ticket` requires a proof that the buyer is part of
+/// the allowlist. Passing `None` opens the lottery to everyone again.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `allowlist_root` - The new allowlist Merkle root, or `None` to remove the allowlist
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_allowlist(ctx: Context<SetAllowlist>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+    msg!("setting lottery allowlist");
+    ctx.accounts.lottery.allowlist_root = allowlist_root;
+    emit_cpi!(AllowlistUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        allowlist_root,
+    });
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::{LotteryPauseUpdated, ProgramPauseUpdated};
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCentralPaused<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLotteryPaused<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Pauses or resumes the whole program
+///
+/// While paused, ticket, draw and claim instructions fail with
+/// `MyError::ProgramPaused` regardless of the per-lottery flag.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `paused` - Whether the program should be paused
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_central_paused(ctx: Context<SetCentralPaused>, paused: bool) -> Result<()> {
+    msg!("setting program paused: {}", paused);
+    ctx.accounts.central_authority.paused = paused;
+    emit_cpi!(ProgramPauseUpdated { paused });
+    Ok(())
+}
+
+/// Pauses or resumes a single lottery
+///
+/// While paused, ticket, draw and claim instructions for this lottery fail
+/// with `MyError::LotteryPaused`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `paused` - Whether the lottery should be paused
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_lottery_paused(ctx: Context<SetLotteryPaused>, paused: bool) -> Result<()> {
+    msg!("setting lottery paused: {}", paused);
+    ctx.accounts.lottery.paused = paused;
+    emit_cpi!(LotteryPauseUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        paused,
+    });
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{DiscountCollection, LotteryStateData, PricingModel, MAX_DISCOUNT_COLLECTIONS};
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::{DiscountCollectionsUpdated, PricingUpdated};
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPricingModel<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDiscountCollections<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Sets how tickets of a lottery are priced
+///
+/// With a pricing model, `buy_ticket` charges the price at the current ticket count,
+/// fixed, rising by a step every `step_size` tickets, or tiered by tickets sold,
+/// less any holder discount. `None` lets buyers choose their deposit.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `pricing` - The pricing model, or `None` to clear it
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_pricing_model(ctx: Context<SetPricingModel>, pricing: Option<PricingModel>) -> Result<()> {
+    msg!("setting pricing model");
+    if let Some(pricing) = &pricing {
+        require!(pricing.is_valid(), MyError::InvalidPricingModel);
+    }
+
+    ctx.accounts.lottery.pricing = pricing.clone();
+    emit_cpi!(PricingUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        pricing,
+    });
+    Ok(())
+}
+
+/// Sets the partner collections whose holders get a discount on tickets
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `discount_collections` - The eligible verified collections and their discount in basis points
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_discount_collections(
+    ctx: Context<SetDiscountCollections>,
+    discount_collections: Vec<DiscountCollection>,
+) -> Result<()> {
+    msg!("setting discount collections");
+    require!(
+        discount_collections.len() <= MAX_DISCOUNT_COLLECTIONS
+            && discount_collections.iter().all(|discount| discount.discount_bps <= 10_000),
+        MyError::InvalidDiscountCollections
+    );
+
+    ctx.accounts.lottery.discount_collections = discount_collections.clone();
+    emit_cpi!(DiscountCollectionsUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        discount_collections,
+    });
+    Ok(())
+}
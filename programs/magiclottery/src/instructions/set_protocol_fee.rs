@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::ProtocolFeeUpdated;
+use crate::CENTRAL_AUTHORITY_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+}
+
+/// Sets the protocol fee taken on secondary ticket sales
+///
+/// A non-zero fee requires a treasury. Lotteries whose royalty and this fee together
+/// exceed the sale price can't be resold until the fee is lowered.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `protocol_fee_bps` - The fee in basis points of the sale price
+/// * `protocol_treasury` - The account receiving the fee
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_protocol_fee(
+    ctx: Context<SetProtocolFee>,
+    protocol_fee_bps: u16,
+    protocol_treasury: Pubkey,
+) -> Result<()> {
+    msg!("setting protocol fee");
+    require!(protocol_fee_bps <= 10_000, MyError::InvalidProtocolFee);
+    require!(protocol_fee_bps == 0 || protocol_treasury != Pubkey::default(), MyError::TreasuryNotSet);
+
+    let central_authority = &mut ctx.accounts.central_authority;
+    central_authority.protocol_fee_bps = protocol_fee_bps;
+    central_authority.protocol_treasury = protocol_treasury;
+
+    emit_cpi!(ProtocolFeeUpdated {
+        protocol_fee_bps,
+        protocol_treasury,
+    });
+    Ok(())
+}
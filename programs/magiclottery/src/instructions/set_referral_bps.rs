@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::ReferralBpsUpdated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetReferralBps<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Sets the share of each ticket price credited to the referrer of the purchase
+///
+/// Referrals can't be enabled on no-loss lotteries, whose payments are refundable in full.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `referral_bps` - The referral share in basis points, `0` disables referrals
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+    msg!("setting lottery referral share");
+    require!(referral_bps <= 10_000, MyError::InvalidReferralBps);
+    // Referral shares aren't refundable, so they would break the no-loss promise
+    require!(referral_bps == 0 || !ctx.accounts.lottery.no_loss, MyError::NoLossReferral);
+    ctx.accounts.lottery.referral_bps = referral_bps;
+    emit_cpi!(ReferralBpsUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        referral_bps,
+    });
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, RefundPolicy};
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::RefundPolicyUpdated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRefundPolicy<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Sets who receives the refund when a ticket is dissolved
+///
+/// The policy is copied into each ticket record at purchase, so changing it only
+/// affects tickets bought afterwards.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `refund_policy` - Whether refunds go to the ticket holder or to the payer
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_policy: RefundPolicy) -> Result<()> {
+    msg!("setting lottery refund policy");
+    ctx.accounts.lottery.refund_policy = refund_policy;
+    emit_cpi!(RefundPolicyUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        refund_policy,
+    });
+    Ok(())
+}
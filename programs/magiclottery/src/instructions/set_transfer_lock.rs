@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TransferLockUpdated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTransferLock<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+}
+
+/// Sets the window around the draw during which tickets are locked
+///
+/// Inside the window tickets can't be dissolved, listed or transferred through this
+/// program, and only their recorded holder can claim them. Nothing stops owners from
+/// transferring or redelegating tickets through Bubblegum directly, as Bubblegum v1
+/// can't freeze them and the ticket lock PDA delegate doesn't restrict the owner; a
+/// ticket moved that way just can't be claimed by its new owner until the window
+/// ends. Tickets can be released with `unlock_ticket` once the window is over or the
+/// lottery is settled. Passing `0` for both timestamps disables the lock.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `starts_at` - Unix timestamp at which the lock window starts
+/// * `ends_at` - Unix timestamp at which the lock window ends
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_transfer_lock(ctx: Context<SetTransferLock>, starts_at: i64, ends_at: i64) -> Result<()> {
+    msg!("setting ticket transfer lock");
+    require!(
+        starts_at < ends_at || (starts_at == 0 && ends_at == 0),
+        MyError::InvalidTransferLock
+    );
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.transfer_lock_starts_at = starts_at;
+    lottery.transfer_lock_ends_at = ends_at;
+
+    emit_cpi!(TransferLockUpdated {
+        lottery: lottery.key(),
+        starts_at,
+        ends_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TreeDelegateUpdated;
+use crate::clones::program_ids::*;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+
+use mpl_bubblegum::instructions::SetTreeDelegateCpiBuilder;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTreeDelegate<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This account is checked in the instruction
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Must be one of the lottery's trees
+    #[account(
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Any key or PDA can be made the tree delegate
+    pub new_tree_delegate: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegates minting into a lottery tree to a dedicated minter
+///
+/// The central authority stays the tree creator; the delegate is recorded on the
+/// lottery and must sign as `tree_creator_or_delegate` for `buy_ticket` and
+/// `redeem_promo` to mint into the tree, so the program no longer mints into it with
+/// the central authority alone. Setting the central authority itself as delegate
+/// revokes the delegation.
+///
+/// The delegate is trusted: Bubblegum also lets it mint directly into the tree,
+/// bypassing payment and the lottery state. Such leaves aren't tickets, as they have
+/// no serial or ticket record, but they take up tree capacity, which `buy_ticket`
+/// reads from the tree config when rolling over.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn set_tree_delegate(ctx: Context<SetTreeDelegate>) -> Result<()> {
+    msg!("setting lottery tree delegate");
+
+    // Prepare the signer seeds for the CPI call
+    let bump_seed = [ctx.bumps.central_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    SetTreeDelegateCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .tree_creator(&ctx.accounts.central_authority.to_account_info())
+        .new_tree_delegate(&ctx.accounts.new_tree_delegate.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .invoke_signed(signer_seeds)?;
+
+    let central_authority = ctx.accounts.central_authority.key();
+    let new_tree_delegate = ctx.accounts.new_tree_delegate.key();
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+    let tree = ctx.accounts.lottery.trees.iter_mut()
+        .find(|tree| tree.merkle_tree == merkle_tree)
+        .ok_or(MyError::InvalidMerkleTree)?;
+    tree.tree_delegate = (new_tree_delegate != central_authority).then_some(new_tree_delegate);
+
+    emit_cpi!(TreeDelegateUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        tree_delegate: ctx.accounts.new_tree_delegate.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::{LotteryStateData, LotteryStatus};
+use crate::global_accounts::yield_vault_account::YieldVault;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::LotterySettled;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED, VAULT_SEED, YIELD_VAULT_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleLottery<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump,
+        constraint = lottery.status == LotteryStatus::Drawn @ MyError::LotteryNotDrawn
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK: This is the PDA holding the lottery funds
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [YIELD_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    /// CHECK: The protocol treasury, receiving the funds left in the vault
+    #[account(
+        mut,
+        address = central_authority.protocol_treasury,
+        constraint = treasury.key() != Pubkey::default() @ MyError::TreasuryNotSet
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Settles a drawn lottery once its claim period is over
+///
+/// The claim period recorded by `reveal_draw` must be over. Whatever is left in the
+/// vault above its rent-exempt minimum, including unclaimed prizes, is swept to the
+/// protocol treasury and no more prizes can be claimed. The principal
+/// of no-loss lotteries stays in the vault for holders to dissolve their tickets.
+///
+/// There is no fallback receiver: the treasury must have been configured with
+/// `set_protocol_fee` before any lottery can be settled.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn settle_lottery(ctx: Context<SettleLottery>) -> Result<()> {
+    msg!("settling lottery");
+
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.lottery.claim_deadline,
+        MyError::ClaimPeriodNotOver
+    );
+
+    let lottery_key = ctx.accounts.lottery.key();
+    let mut swept = ctx.accounts.vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if ctx.accounts.lottery.no_loss {
+        let yield_vault = ctx.accounts.yield_vault.as_ref()
+            .ok_or(MyError::MissingYieldVault)?;
+        swept = yield_vault.prize_funds(swept);
+    }
+
+    if swept > 0 {
+        let bump_seed = [ctx.bumps.vault];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_SEED,
+            lottery_key.as_ref(),
+            &bump_seed,
+        ]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept,
+        )?;
+    }
+
+    ctx.accounts.lottery.status = LotteryStatus::Settled;
+
+    emit_cpi!(LotterySettled {
+        lottery: lottery_key,
+        treasury: ctx.accounts.treasury.key(),
+        swept,
+    });
+
+    Ok(())
+}
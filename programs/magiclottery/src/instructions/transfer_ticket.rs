@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketTransferred;
+use crate::clones::program_ids::*;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::instructions::TransferCpiBuilder;
+use crate::{LOTTERY_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(serial: u64)]
+pub struct TransferTicket<'info> {
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: The current leaf delegate, checked against the leaf by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: The wallet receiving the ticket
+    pub new_leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is modified in the downstream program
+    #[account(
+        mut,
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub tree_config: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers a ticket to another wallet outside the transfer lock window
+///
+/// The ticket record follows the transfer, so the new owner is the recorded holder
+/// during the next lock window. Tickets transferred through Bubblegum directly keep
+/// the previous holder on their record, so their new owner can't claim them while
+/// the window lasts; outside the window the leaf owner is all that counts.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
+/// * `root` - The root hash of the merkle tree
+/// * `nonce` - The nonce of the leaf
+/// * `index` - The index of the leaf in the merkle tree
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn transfer_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferTicket<'info>>,
+    serial: u64,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    msg!("transferring ticket");
+
+    let lottery = &ctx.accounts.lottery;
+    require!(!lottery.is_transfer_locked(Clock::get()?.unix_timestamp), MyError::TicketsLocked);
+
+    let (leaf, _) = ticket_leaf(lottery, &ctx.accounts.merkle_tree.key(), serial, ctx.remaining_accounts, 0)?;
+
+    TransferCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.leaf_owner.to_account_info(), true)
+        .leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info(), false)
+        .new_leaf_owner(&ctx.accounts.new_leaf_owner.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .invoke()?;
+
+    ctx.accounts.ticket_record.holder = ctx.accounts.new_leaf_owner.key();
+
+    emit_cpi!(TicketTransferred {
+        lottery: lottery.key(),
+        from: ctx.accounts.leaf_owner.key(),
+        to: ctx.accounts.new_leaf_owner.key(),
+        serial,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::TicketUnlocked;
+use crate::clones::program_ids::*;
+use crate::utils::tree::ticket_leaf;
+use mpl_bubblegum::instructions::DelegateCpiBuilder;
+use crate::{LOTTERY_SEED, TICKET_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(serial: u64)]
+pub struct UnlockTicket<'info> {
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, lottery.key().as_ref(), &serial.to_le_bytes()],
+        bump
+    )]
+    pub ticket_record: Account<'info, TicketRecord>,
+
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: The current leaf delegate, checked against the leaf by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: This account is modified in the downstream program
+    #[account(
+        mut,
+        constraint = lottery.has_tree(&merkle_tree.key()) @ MyError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub tree_config: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub bubblegum_program: Program<'info, MplBubblegum>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Releases a ticket from the transfer lock
+///
+/// Tickets are minted with the ticket lock PDA as leaf delegate, which doesn't restrict
+/// their owner in any way. Once a configured lock window is over or the lottery is
+/// settled or cancelled, the leaf owner takes the delegation back and is recorded as
+/// the holder. The ticket record is marked unlocked for that window only: a window
+/// set later with `set_transfer_lock` gates claims on the recorded holder again.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `serial` - The serial number of the ticket
+/// * `root` - The root hash of the merkle tree
+/// * `nonce` - The nonce of the leaf
+/// * `index` - The index of the leaf in the merkle tree
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn unlock_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnlockTicket<'info>>,
+    serial: u64,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    msg!("unlocking ticket");
+
+    let lottery = &ctx.accounts.lottery;
+    require!(lottery.can_unlock_tickets(Clock::get()?.unix_timestamp), MyError::TicketsLocked);
+
+    let (leaf, _) = ticket_leaf(lottery, &ctx.accounts.merkle_tree.key(), serial, ctx.remaining_accounts, 0)?;
+
+    // Hand the delegation back to the owner
+    DelegateCpiBuilder::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+    )
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.leaf_owner.to_account_info())
+        .previous_leaf_delegate(&ctx.accounts.leaf_delegate.to_account_info())
+        .new_leaf_delegate(&ctx.accounts.leaf_owner.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .add_remaining_accounts(&leaf.proof)
+        .root(root)
+        .data_hash(leaf.data_hash)
+        .creator_hash(leaf.creator_hash)
+        .nonce(nonce)
+        .index(index)
+        .invoke()?;
+
+    let ticket_record = &mut ctx.accounts.ticket_record;
+    ticket_record.unlocked_for = lottery.transfer_lock_ends_at;
+    ticket_record.holder = ctx.accounts.leaf_owner.key();
+
+    emit_cpi!(TicketUnlocked {
+        lottery: lottery.key(),
+        leaf_owner: ctx.accounts.leaf_owner.key(),
+        serial,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::global_accounts::central_account::CentralStateData;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+use crate::events::lottery_events::LotteryMetadataUpdated;
+use crate::{CENTRAL_AUTHORITY_SEED, LOTTERY_SEED};
+use anchor_spl::metadata::{
+    update_metadata_accounts_v2, Metadata, UpdateMetadataAccountsV2,
+    mpl_token_metadata::types::DataV2,
+};
+use mpl_token_metadata::accounts::Metadata as MetadataAccount;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLotteryMetadata<'info> {
+    #[account(
+        constraint = authorizer_wallet.key() == central_authority.authorizer_wallet @ MyError::UnauthorizedWallet
+    )]
+    pub authorizer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [CENTRAL_AUTHORITY_SEED],
+        bump
+    )]
+    pub central_authority: Account<'info, CentralStateData>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, lottery.collection_mint.as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, LotteryStateData>,
+
+    /// CHECK - address
+    #[account(
+        mut,
+        address = MetadataAccount::find_pda(&lottery.collection_mint).0,
+    )]
+    pub metadata_account: AccountInfo<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Updates the metadata of a lottery collection NFT
+///
+/// Lets operators fix the collection name or URI, or publish results artwork after
+/// the draw. Only the collection NFT changes: tickets keep the symbol and base URI
+/// stored on the lottery, which claims rely on to rebuild ticket metadata.
+///
+/// # Arguments
+///
+/// * `ctx` - The context struct containing the accounts required for the operation
+/// * `name` - The new name of the lottery collection
+/// * `symbol` - The new symbol of the lottery collection
+/// * `uri` - The new URI of the lottery collection metadata
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the operation is successful, otherwise returns an error
+pub fn update_lottery_metadata(
+    ctx: Context<UpdateLotteryMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    msg!("updating collection metadata");
+
+    // Prepare the signer seeds for the CPI call
+    let bump_seed = [ctx.bumps.central_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        UpdateMetadataAccountsV2 {
+            metadata: ctx.accounts.metadata_account.to_account_info(),
+            update_authority: ctx.accounts.central_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    let data_v2 = DataV2 {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    update_metadata_accounts_v2(cpi_context, None, Some(data_v2), None, None)?;
+
+    emit_cpi!(LotteryMetadataUpdated {
+        lottery: ctx.accounts.lottery.key(),
+        name,
+        symbol,
+        uri,
+    });
+
+    Ok(())
+}
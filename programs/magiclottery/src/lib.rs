@@ -2,12 +2,37 @@
 
 use anchor_lang::{prelude::*, solana_program::pubkey::Pubkey};
 mod errors;
+pub mod events;
 mod clones;
 mod global_accounts;
 mod instructions;
+mod utils;
 use crate::instructions::*;
+use crate::utils::voucher::PurchaseVoucher;
+use crate::utils::allowlist::AllowlistProof;
+use crate::global_accounts::lottery_account::{DiscountCollection, PickConfig, PricingModel, RefundPolicy, RoyaltyConfig};
 
 pub const CENTRAL_AUTHORITY_SEED: &[u8] = b"B_central_authority";
+pub const LOTTERY_SEED: &[u8] = b"B_lottery";
+pub const VOUCHER_SEED: &[u8] = b"B_voucher";
+pub const BUYER_SEED: &[u8] = b"B_buyer";
+pub const VAULT_SEED: &[u8] = b"B_vault";
+pub const PICKS_SEED: &[u8] = b"B_picks";
+pub const TICKET_LOCK_SEED: &[u8] = b"B_ticket_lock";
+pub const LISTING_SEED: &[u8] = b"B_listing";
+pub const TICKET_SEED: &[u8] = b"B_ticket";
+pub const REFERRER_SEED: &[u8] = b"B_referrer";
+pub const PROMO_SEED: &[u8] = b"B_promo";
+pub const PROMO_CODE_SEED: &[u8] = b"B_promo_code";
+pub const YIELD_VAULT_SEED: &[u8] = b"B_yield_vault";
+/// Slots between `commit_draw` and the slot whose hash seeds the draw
+pub const DRAW_SLOT_DELAY: u64 = 8;
+/// Slots after the draw slot during which the draw can be revealed, well within the SlotHashes history
+pub const REVEAL_WINDOW_SLOTS: u64 = 300;
+/// Seconds after the reveal during which pick mode winners register before claims open
+pub const WIN_REGISTRATION_PERIOD: i64 = 24 * 60 * 60;
+/// Seconds after claims open during which prizes can be claimed before the lottery can be settled
+pub const CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
 declare_id!("ML1sC4hP2dtyNHSL4QP4KUkqjyqL3cFJWHLmQZdZ3yN");
 
 /// Main program module for Magic Lottery
@@ -21,23 +46,199 @@ pub mod magic_lottery {
     }
 
     /// Create a new lottery
-    pub fn create_lottery<'info>(ctx: Context<'_, '_, '_, 'info, CreateLottery<'info>>, name: String, symbol: String, uri: String) -> Result<()> { 
-        create_lottery::create_lottery(ctx, name, symbol, uri)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_lottery<'info>(ctx: Context<'_, '_, '_, 'info, CreateLottery<'info>>, name: String, symbol: String, uri: String, base_uri: String, requires_cosign: bool, pick_config: Option<PickConfig>, royalty_config: RoyaltyConfig) -> Result<()> { 
+        create_lottery::create_lottery(ctx, name, symbol, uri, base_uri, requires_cosign, pick_config, royalty_config)
     }   
 
     /// Buy a lottery ticket
-    pub fn buy_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>, name: String, symbol: String, uri: String, seller_fee_basis_points: u16, payment_amount: u64) -> Result<()> { 
-        buy_ticket::buy_ticket(ctx, name, symbol, uri, seller_fee_basis_points, payment_amount)
+    pub fn buy_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>, payment_amount: u64, voucher: Option<PurchaseVoucher>, allowlist_proof: Option<AllowlistProof>, picks: Vec<u8>, max_price: u64) -> Result<TicketReceipt> { 
+        buy_ticket::buy_ticket(ctx, payment_amount, voucher, allowlist_proof, picks, max_price)
     } 
     
     /// Dissolve a ticket
-    pub fn disolve_ticket<'info>(ctx: Context<'_, '_, '_, 'info, DisolveTicket<'info>>, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32) -> Result<()> { 
-        disolve_ticket::disolve_ticket(ctx, root, data_hash, creator_hash, nonce, index)
+    pub fn disolve_ticket<'info>(ctx: Context<'_, '_, '_, 'info, DisolveTicket<'info>>, serial: u64, root: [u8; 32], nonce: u64, index: u32) -> Result<()> { 
+        disolve_ticket::disolve_ticket(ctx, serial, root, nonce, index)
     } 
 
     /// Create a lottery tree
-    pub fn create_tree<'info>(ctx: Context<'_, '_, '_, 'info, CreateLotteryTree<'info>>, max_depth: u32, max_buffer_size: u32) -> Result<()> { 
-        create_tree::create_lottery_tree(ctx, max_depth, max_buffer_size)
+    pub fn create_tree<'info>(ctx: Context<'_, '_, '_, 'info, CreateLotteryTree<'info>>, max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> Result<()> { 
+        create_tree::create_lottery_tree(ctx, max_depth, max_buffer_size, canopy_depth)
     } 
+
+    /// Pause or resume the whole program
+    pub fn set_central_paused(ctx: Context<SetCentralPaused>, paused: bool) -> Result<()> {
+        set_paused::set_central_paused(ctx, paused)
+    }
+
+    /// Pause or resume a single lottery
+    pub fn set_lottery_paused(ctx: Context<SetLotteryPaused>, paused: bool) -> Result<()> {
+        set_paused::set_lottery_paused(ctx, paused)
+    }
+
+    /// Set or clear the allowlist Merkle root of a lottery
+    pub fn set_allowlist(ctx: Context<SetAllowlist>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        set_allowlist::set_allowlist(ctx, allowlist_root)
+    }
+
+    /// Close ticket sales and commit to the draw randomness
+    pub fn commit_draw(ctx: Context<CommitDraw>, commitment: [u8; 32]) -> Result<()> {
+        commit_draw::commit_draw(ctx, commitment)
+    }
+
+    /// Reveal the draw secret and select the winners
+    pub fn reveal_draw(ctx: Context<RevealDraw>, secret: [u8; 32]) -> Result<()> {
+        reveal_draw::reveal_draw(ctx, secret)
+    }
+
+    /// Cancel a lottery whose draw was not revealed in time
+    pub fn cancel_expired_draw(ctx: Context<CancelExpiredDraw>) -> Result<()> {
+        cancel_lottery::cancel_expired_draw(ctx)
+    }
+
+    /// Register a winning pick mode ticket for its prize tier
+    pub fn register_win(ctx: Context<RegisterWin>, serial: u64) -> Result<()> {
+        register_win::register_win(ctx, serial)
+    }
+
+    /// Claim the prize of a winning ticket
+    pub fn claim_prize<'info>(ctx: Context<'_, '_, '_, 'info, ClaimPrize<'info>>, serial: u64, root: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        claim_prize::claim_prize(ctx, serial, root, nonce, index)
+    }
+
+    /// Delegate minting into a lottery tree
+    pub fn set_tree_delegate(ctx: Context<SetTreeDelegate>) -> Result<()> {
+        set_tree_delegate::set_tree_delegate(ctx)
+    }
+
+    /// Settle a drawn lottery and sweep its remaining funds
+    pub fn settle_lottery(ctx: Context<SettleLottery>) -> Result<()> {
+        settle_lottery::settle_lottery(ctx)
+    }
+
+    /// Cancel a lottery before its draw
+    pub fn cancel_lottery(ctx: Context<CancelLottery>) -> Result<()> {
+        cancel_lottery::cancel_lottery(ctx)
+    }
+
+    /// Close a finished lottery and reclaim its rent
+    pub fn close_lottery(ctx: Context<CloseLottery>) -> Result<()> {
+        close_lottery::close_lottery(ctx)
+    }
+
+    /// Close a buyer's ticket counter and reclaim its rent
+    pub fn close_buyer_record(ctx: Context<CloseBuyerRecord>) -> Result<()> {
+        close_buyer_record::close_buyer_record(ctx)
+    }
+
+    /// Update the metadata of a lottery collection
+    pub fn update_lottery_metadata(ctx: Context<UpdateLotteryMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+        update_lottery_metadata::update_lottery_metadata(ctx, name, symbol, uri)
+    }
+
+    /// Set the ticket transfer lock window of a lottery
+    pub fn set_transfer_lock(ctx: Context<SetTransferLock>, starts_at: i64, ends_at: i64) -> Result<()> {
+        set_transfer_lock::set_transfer_lock(ctx, starts_at, ends_at)
+    }
+
+    /// Release a ticket from the transfer lock
+    pub fn unlock_ticket<'info>(ctx: Context<'_, '_, '_, 'info, UnlockTicket<'info>>, serial: u64, root: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        unlock_ticket::unlock_ticket(ctx, serial, root, nonce, index)
+    }
+
+    /// Transfer a ticket to another wallet, keeping its record in sync
+    pub fn transfer_ticket<'info>(ctx: Context<'_, '_, '_, 'info, TransferTicket<'info>>, serial: u64, root: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        transfer_ticket::transfer_ticket(ctx, serial, root, nonce, index)
+    }
+
+    /// Set the protocol fee taken on secondary ticket sales
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, protocol_fee_bps: u16, protocol_treasury: Pubkey) -> Result<()> {
+        set_protocol_fee::set_protocol_fee(ctx, protocol_fee_bps, protocol_treasury)
+    }
+
+    /// List a ticket for sale
+    pub fn list_ticket<'info>(ctx: Context<'_, '_, '_, 'info, ListTicket<'info>>, serial: u64, price: u64, root: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        list_ticket::list_ticket(ctx, serial, price, root, nonce, index)
+    }
+
+    /// Buy a listed ticket
+    pub fn buy_listed_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyListedTicket<'info>>, root: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        buy_listed_ticket::buy_listed_ticket(ctx, root, nonce, index)
+    }
+
+    /// Cancel a ticket listing
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        cancel_listing::cancel_listing(ctx)
+    }
+
+    /// Set who receives the refund of dissolved tickets
+    pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_policy: RefundPolicy) -> Result<()> {
+        set_refund_policy::set_refund_policy(ctx, refund_policy)
+    }
+
+    /// Close a ticket record and reclaim its rent
+    pub fn close_ticket_record(ctx: Context<CloseTicketRecord>) -> Result<()> {
+        close_ticket_record::close_ticket_record(ctx)
+    }
+
+    /// Close a voucher redemption counter and reclaim its rent
+    pub fn close_voucher_record(ctx: Context<CloseVoucherRecord>) -> Result<()> {
+        close_voucher_record::close_voucher_record(ctx)
+    }
+
+    /// Set the referral share of a lottery
+    pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+        set_referral_bps::set_referral_bps(ctx, referral_bps)
+    }
+
+    /// Withdraw referral rewards
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        claim_referral_rewards::claim_referral_rewards(ctx)
+    }
+
+    /// Create a promo campaign
+    pub fn create_promo_campaign(ctx: Context<CreatePromoCampaign>, campaign_id: u64, codes_root: [u8; 32], budget: u32) -> Result<()> {
+        create_promo_campaign::create_promo_campaign(ctx, campaign_id, codes_root, budget)
+    }
+
+    /// Redeem a promo code for a free ticket
+    pub fn redeem_promo<'info>(ctx: Context<'_, '_, '_, 'info, RedeemPromo<'info>>, code: String, proof: Vec<[u8; 32]>, allowlist_proof: Option<AllowlistProof>, picks: Vec<u8>) -> Result<TicketReceipt> {
+        redeem_promo::redeem_promo(ctx, code, proof, allowlist_proof, picks)
+    }
+
+    /// Close a promo code redemption and reclaim its rent
+    pub fn close_promo_redemption(ctx: Context<ClosePromoRedemption>) -> Result<()> {
+        close_promo_redemption::close_promo_redemption(ctx)
+    }
+
+    /// Set the pricing model of a lottery
+    pub fn set_pricing_model(ctx: Context<SetPricingModel>, pricing: Option<PricingModel>) -> Result<()> {
+        set_pricing::set_pricing_model(ctx, pricing)
+    }
+
+    /// Set the partner collections whose holders get a discount
+    pub fn set_discount_collections(ctx: Context<SetDiscountCollections>, discount_collections: Vec<DiscountCollection>) -> Result<()> {
+        set_pricing::set_discount_collections(ctx, discount_collections)
+    }
+
+    /// Enable no-loss mode on a lottery
+    pub fn enable_no_loss(ctx: Context<EnableNoLoss>) -> Result<()> {
+        enable_no_loss::enable_no_loss(ctx)
+    }
+
+    /// Deposit lottery principal into the yield source
+    pub fn deposit_to_yield(ctx: Context<DepositToYield>, amount: u64) -> Result<()> {
+        manage_yield::deposit_to_yield(ctx, amount)
+    }
+
+    /// Withdraw lottery principal from the yield source
+    pub fn withdraw_from_yield(ctx: Context<WithdrawFromYield>, amount: u64) -> Result<()> {
+        manage_yield::withdraw_from_yield(ctx, amount)
+    }
+
+    /// Harvest the yield accrued on the lottery principal
+    pub fn harvest_yield(ctx: Context<HarvestYield>) -> Result<()> {
+        manage_yield::harvest_yield(ctx)
+    }
 }
 
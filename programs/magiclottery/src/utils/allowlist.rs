@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::global_accounts::buyer_account::BuyerRecord;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+
+/// Proof that a buyer is part of a lottery allowlist.
+///
+/// Leaves are `keccak(buyer || max_tickets)` with `max_tickets` encoded little-endian;
+/// a `max_tickets` of zero means the buyer has no per-address cap. Inner nodes hash
+/// the sorted pair of their children.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    pub max_tickets: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Checks that `buyer` with the cap encoded in `allowlist_proof` is included under `root`.
+///
+/// # Arguments
+///
+/// * `root` - The allowlist Merkle root stored on the lottery
+/// * `buyer` - The public key of the buyer
+/// * `allowlist_proof` - The leaf cap and sibling hashes from the leaf up to the root
+///
+/// # Returns
+///
+/// * `bool` - Returns true if the proof resolves to `root`
+pub fn verify_allowlist_proof(root: &[u8; 32], buyer: &Pubkey, allowlist_proof: &AllowlistProof) -> bool {
+    let mut node = keccak::hashv(&[
+        buyer.as_ref(),
+        &allowlist_proof.max_tickets.to_le_bytes(),
+    ]).to_bytes();
+
+    for sibling in allowlist_proof.proof.iter() {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    node == *root
+}
+
+/// Enforces the allowlist of `lottery` and its per-address cap, counting the ticket on the buyer record.
+///
+/// Allowlisted lotteries require a proof that `buyer` is on the allowlist. When the leaf
+/// caps the buyer's tickets the buyer record is required, and the ticket must fit the cap.
+///
+/// # Arguments
+///
+/// * `lottery` - The lottery the ticket is minted from
+/// * `lottery_key` - The address of the lottery
+/// * `buyer` - The public key of the buyer
+/// * `allowlist_proof` - The allowlist proof, required when the lottery has an allowlist root
+/// * `buyer_record` - The buyer's ticket counter, required when the buyer is capped
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the buyer may get the ticket, otherwise returns an error
+pub fn enforce_allowlist(
+    lottery: &LotteryStateData,
+    lottery_key: Pubkey,
+    buyer: Pubkey,
+    allowlist_proof: Option<AllowlistProof>,
+    buyer_record: Option<&mut BuyerRecord>,
+) -> Result<()> {
+    let mut max_tickets = 0;
+    if let Some(allowlist_root) = lottery.allowlist_root {
+        let allowlist_proof = allowlist_proof.ok_or(MyError::NotAllowlisted)?;
+        require!(
+            verify_allowlist_proof(&allowlist_root, &buyer, &allowlist_proof),
+            MyError::NotAllowlisted
+        );
+        max_tickets = allowlist_proof.max_tickets;
+    }
+
+    if max_tickets > 0 {
+        require!(buyer_record.is_some(), MyError::MissingBuyerRecord);
+    }
+
+    if let Some(buyer_record) = buyer_record {
+        require!(
+            max_tickets == 0 || buyer_record.tickets_bought < max_tickets,
+            MyError::AllowlistCapReached
+        );
+        buyer_record.lottery = lottery_key;
+        buyer_record.buyer = buyer;
+        buyer_record.tickets_bought += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(buyer: &Pubkey, max_tickets: u32) -> [u8; 32] {
+        keccak::hashv(&[buyer.as_ref(), &max_tickets.to_le_bytes()]).to_bytes()
+    }
+
+    fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[a, b]).to_bytes()
+        } else {
+            keccak::hashv(&[b, a]).to_bytes()
+        }
+    }
+
+    fn proof(max_tickets: u32, proof: Vec<[u8; 32]>) -> AllowlistProof {
+        AllowlistProof { max_tickets, proof }
+    }
+
+    #[test]
+    fn single_leaf_allowlist_is_its_own_root() {
+        let buyer = Pubkey::new_unique();
+        let root = leaf(&buyer, 0);
+
+        assert!(verify_allowlist_proof(&root, &buyer, &proof(0, vec![])));
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &proof(0, vec![])));
+    }
+
+    #[test]
+    fn multi_level_proofs_resolve_to_the_root() {
+        let buyers: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = buyers.iter().zip([0, 1, 2, 3]).map(|(buyer, cap)| leaf(buyer, cap)).collect();
+        let left = parent(&leaves[0], &leaves[1]);
+        let right = parent(&leaves[2], &leaves[3]);
+        let root = parent(&left, &right);
+
+        assert!(verify_allowlist_proof(&root, &buyers[0], &proof(0, vec![leaves[1], right])));
+        assert!(verify_allowlist_proof(&root, &buyers[1], &proof(1, vec![leaves[0], right])));
+        assert!(verify_allowlist_proof(&root, &buyers[2], &proof(2, vec![leaves[3], left])));
+        assert!(verify_allowlist_proof(&root, &buyers[3], &proof(3, vec![leaves[2], left])));
+    }
+
+    #[test]
+    fn wrong_leaf_is_rejected() {
+        let buyers: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let root = parent(&leaf(&buyers[0], 5), &leaf(&buyers[1], 0));
+        let siblings = vec![leaf(&buyers[1], 0)];
+
+        // Another buyer, or the right buyer claiming a different cap
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &proof(5, siblings.clone())));
+        assert!(!verify_allowlist_proof(&root, &buyers[0], &proof(0, siblings.clone())));
+        assert!(verify_allowlist_proof(&root, &buyers[0], &proof(5, siblings)));
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use mpl_token_metadata::accounts::Metadata;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::errors::errors::MyError;
+
+/// Discount a holder of the NFT in `token_account` gets on a ticket of `lottery`.
+///
+/// The NFT must belong to a verified collection listed in the lottery discount collections.
+///
+/// # Arguments
+///
+/// * `lottery` - The lottery the ticket is bought from
+/// * `token_account` - The buyer's token account holding the NFT
+/// * `metadata_info` - The metadata account of the NFT mint
+///
+/// # Returns
+///
+/// * `Result<u16>` - The discount in basis points, or an error if the NFT isn't eligible
+pub fn holder_discount_bps(
+    lottery: &LotteryStateData,
+    token_account: &TokenAccount,
+    metadata_info: &AccountInfo,
+) -> Result<u16> {
+    require_keys_eq!(*metadata_info.owner, mpl_token_metadata::ID, MyError::InvalidHolderProof);
+    require_keys_eq!(
+        metadata_info.key(),
+        Metadata::find_pda(&token_account.mint).0,
+        MyError::InvalidHolderProof
+    );
+
+    let metadata = Metadata::safe_deserialize(&metadata_info.try_borrow_data()?)
+        .map_err(|_| MyError::InvalidHolderProof)?;
+    let collection = metadata.collection
+        .filter(|collection| collection.verified)
+        .ok_or(MyError::CollectionNotEligible)?;
+
+    lottery.discount_collections.iter()
+        .find(|discount| discount.collection == collection.key)
+        .map(|discount| discount.discount_bps)
+        .ok_or_else(|| error!(MyError::CollectionNotEligible))
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::errors::errors::MyError;
+
+/// Reads the hash of `slot` from the SlotHashes sysvar account, or of the first slot
+/// after it if it was skipped.
+///
+/// The sysvar is too large to deserialize on-chain, so its entries are read directly:
+/// a `u64` entry count followed by `(slot: u64, hash: [u8; 32])` entries, most recent
+/// first. An older entry must be present too, proving no earlier slot at or after
+/// `slot` has been evicted from the history.
+pub fn slot_hash_at(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let count = data.get(..8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let mut hash = None;
+    for entry in data[8..].chunks_exact(40).take(count as usize) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot < slot {
+            return hash.ok_or(error!(MyError::SlotHashUnavailable));
+        }
+        hash = Some(entry[8..].try_into().unwrap());
+    }
+    err!(MyError::SlotHashUnavailable)
+}
+
+/// Combines the revealed secret with the slot hash and lottery key into the draw seed
+pub fn draw_seed(secret: &[u8; 32], slot_hash: &[u8; 32], lottery: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[secret, slot_hash, lottery.as_ref()]).to_bytes()
+}
+
+fn random_u64(seed: &[u8; 32], round: u64) -> u64 {
+    let hash = keccak::hashv(&[seed, &round.to_le_bytes()]).to_bytes();
+    u64::from_le_bytes(hash[..8].try_into().unwrap())
+}
+
+/// Picks the winning ticket serial in `1..=tickets_minted` for the given draw attempt
+///
+/// Dissolved tickets can be drawn, in which case the next attempt redraws.
+pub fn draw_winning_ticket(seed: &[u8; 32], attempt: u64, tickets_minted: u64) -> u64 {
+    1 + random_u64(seed, attempt) % tickets_minted
+}
+
+/// Picks `pick_count` distinct winning numbers in `1..=pick_max`, sorted ascending
+pub fn draw_winning_numbers(seed: &[u8; 32], pick_count: u8, pick_max: u8) -> Vec<u8> {
+    let mut numbers: Vec<u8> = Vec::with_capacity(pick_count as usize);
+    let mut round = 0;
+    while numbers.len() < pick_count as usize {
+        let number = 1 + (random_u64(seed, round) % pick_max as u64) as u8;
+        if !numbers.contains(&number) {
+            numbers.push(number);
+        }
+        round += 1;
+    }
+    numbers.sort_unstable();
+    numbers
+}
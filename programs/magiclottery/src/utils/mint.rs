@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::instructions::MintToCollectionV1CpiBuilder;
+use mpl_bubblegum::utils::get_asset_id;
+use crate::global_accounts::lottery_account::LotteryStateData;
+use crate::global_accounts::ticket_picks_account::TicketPicks;
+use crate::global_accounts::ticket_record_account::TicketRecord;
+use crate::instructions::buy_ticket::TicketReceipt;
+use crate::utils::tree::select_tree;
+use crate::errors::errors::MyError;
+use crate::CENTRAL_AUTHORITY_SEED;
+
+/// Accounts taking part in the Bubblegum mint of a ticket
+pub struct MintTicketAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub central_authority: AccountInfo<'info>,
+    pub leaf_owner: AccountInfo<'info>,
+    pub ticket_lock: AccountInfo<'info>,
+    pub merkle_tree: AccountInfo<'info>,
+    pub tree_config: AccountInfo<'info>,
+    pub tree_delegate: Option<AccountInfo<'info>>,
+    pub collection_mint: AccountInfo<'info>,
+    pub collection_metadata: AccountInfo<'info>,
+    pub edition_account: AccountInfo<'info>,
+    pub bubblegum_signer: AccountInfo<'info>,
+    pub log_wrapper: AccountInfo<'info>,
+    pub compression_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub bubblegum_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Mints the next ticket of a lottery to `leaf_owner`
+///
+/// Rolls over to the next tree once Bubblegum reports the current one full, numbers
+/// the ticket, fills in its picks and record, and mints it into the collection with
+/// the ticket lock PDA as leaf delegate.
+///
+/// The ticket lock PDA gives no protection: it never signs, and Bubblegum lets the
+/// leaf owner redelegate or transfer the ticket at any time. The only guarantee of
+/// the transfer lock is that claims during the window are gated on the holder
+/// recorded on the ticket record, see `TicketRecord::is_usable_by`.
+///
+/// # Arguments
+///
+/// * `lottery` - The lottery the ticket belongs to
+/// * `lottery_key` - The address of the lottery
+/// * `accounts` - The accounts of the Bubblegum mint
+/// * `full_tree_configs` - The tree configs of the trees rolled over, see `select_tree`
+/// * `central_authority_bump` - The bump of the central authority, signing the mint
+/// * `ticket_picks` - The picks account of the ticket, required in pick mode
+/// * `picks` - The player's numbers in pick mode, empty for raffles
+/// * `ticket_record` - The record of the ticket
+/// * `price` - The refundable price of the ticket
+///
+/// # Returns
+///
+/// * `Result<TicketReceipt>` - The receipt identifying the minted ticket
+#[allow(clippy::too_many_arguments)]
+pub fn mint_ticket<'info>(
+    lottery: &mut LotteryStateData,
+    lottery_key: Pubkey,
+    accounts: MintTicketAccounts<'info>,
+    full_tree_configs: &[AccountInfo<'info>],
+    central_authority_bump: u8,
+    ticket_picks: Option<&mut TicketPicks>,
+    picks: Vec<u8>,
+    ticket_record: &mut TicketRecord,
+    price: u64,
+) -> Result<TicketReceipt> {
+    select_tree(
+        lottery,
+        &accounts.merkle_tree.key(),
+        &accounts.tree_config,
+        full_tree_configs,
+        accounts.tree_delegate.as_ref().map(|tree_delegate| tree_delegate.key()),
+    )?;
+
+    // Tickets are numbered from 1 in mint order
+    lottery.tickets_minted += 1;
+    lottery.collection_size += 1;
+    let serial = lottery.tickets_minted;
+    let metadata = lottery.ticket_metadata(serial);
+
+    if lottery.is_pick_mode() {
+        let ticket_picks = ticket_picks.ok_or(MyError::MissingTicketPicks)?;
+        ticket_picks.lottery = lottery_key;
+        ticket_picks.serial = serial;
+        ticket_picks.picks = picks;
+        ticket_picks.registered = false;
+    }
+
+    // Record who paid and who received the ticket, they differ for gifts
+    ticket_record.lottery = lottery_key;
+    ticket_record.serial = serial;
+    ticket_record.payer = accounts.payer.key();
+    ticket_record.recipient = accounts.leaf_owner.key();
+    ticket_record.price = price;
+    ticket_record.refund_policy = lottery.refund_policy;
+    ticket_record.unlocked_for = 0;
+    ticket_record.holder = accounts.leaf_owner.key();
+
+    // The next leaf index is the tree's mint count before minting
+    let leaf_index = TreeConfig::try_from(&accounts.tree_config)?.num_minted;
+    let asset_id = get_asset_id(&accounts.merkle_tree.key(), leaf_index);
+
+    let bump_seed = [central_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        CENTRAL_AUTHORITY_SEED,
+        &bump_seed,
+    ]];
+
+    let tree_creator_or_delegate = accounts.tree_delegate.as_ref()
+        .unwrap_or(&accounts.central_authority);
+
+    MintToCollectionV1CpiBuilder::new(&accounts.bubblegum_program)
+        .tree_config(&accounts.tree_config)
+        .leaf_owner(&accounts.leaf_owner)
+        .leaf_delegate(&accounts.ticket_lock)
+        .merkle_tree(&accounts.merkle_tree)
+        .payer(&accounts.payer)
+        .tree_creator_or_delegate(tree_creator_or_delegate)
+        .collection_authority(&accounts.central_authority)
+        .collection_authority_record_pda(Some(&accounts.bubblegum_program))
+        .collection_mint(&accounts.collection_mint)
+        .collection_metadata(&accounts.collection_metadata)
+        .collection_edition(&accounts.edition_account)
+        .bubblegum_signer(&accounts.bubblegum_signer)
+        .log_wrapper(&accounts.log_wrapper)
+        .compression_program(&accounts.compression_program)
+        .token_metadata_program(&accounts.token_metadata_program)
+        .system_program(&accounts.system_program)
+        .metadata(metadata)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(TicketReceipt {
+        serial,
+        merkle_tree: accounts.merkle_tree.key(),
+        leaf_index,
+        asset_id,
+    })
+}
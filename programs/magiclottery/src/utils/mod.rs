@@ -0,0 +1,8 @@
+pub mod voucher;
+pub mod allowlist;
+pub mod draw;
+pub mod tree;
+pub mod promo;
+pub mod discount;
+pub mod yield_source;
+pub mod mint;
\ No newline at end of file
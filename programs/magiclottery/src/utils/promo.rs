@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Hash of a promo code, keying its redemption so that it can only be redeemed once
+pub fn promo_code_hash(code: &str) -> [u8; 32] {
+    keccak::hash(code.as_bytes()).to_bytes()
+}
+
+/// Leaf of the campaign Merkle tree: `keccak(redeemer || code)`.
+///
+/// Binding the code to the wallet it was issued to keeps anyone who sees it in a
+/// pending redemption from redeeming it first.
+pub fn promo_leaf(redeemer: &Pubkey, code: &str) -> [u8; 32] {
+    keccak::hashv(&[redeemer.as_ref(), code.as_bytes()]).to_bytes()
+}
+
+/// Checks that the promo code leaf `leaf` is included under `root`.
+///
+/// Inner nodes hash the sorted pair of their children, as for allowlists.
+///
+/// # Arguments
+///
+/// * `root` - The codes Merkle root stored on the campaign
+/// * `leaf` - The leaf of the redeemer and code, see `promo_leaf`
+/// * `proof` - The sibling hashes from the leaf up to the root
+///
+/// # Returns
+///
+/// * `bool` - Returns true if the proof resolves to `root`
+pub fn verify_promo_code(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+    let mut node = *leaf;
+
+    for sibling in proof.iter() {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    node == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_only_verify_for_their_redeemer() {
+        let redeemers: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let leaves = [promo_leaf(&redeemers[0], "WELCOME"), promo_leaf(&redeemers[1], "FRIENDS")];
+        let root = if leaves[0] <= leaves[1] {
+            keccak::hashv(&[&leaves[0], &leaves[1]]).to_bytes()
+        } else {
+            keccak::hashv(&[&leaves[1], &leaves[0]]).to_bytes()
+        };
+
+        assert!(verify_promo_code(&root, &promo_leaf(&redeemers[0], "WELCOME"), &[leaves[1]]));
+        assert!(verify_promo_code(&root, &promo_leaf(&redeemers[1], "FRIENDS"), &[leaves[0]]));
+        // A copied code doesn't verify for another wallet
+        assert!(!verify_promo_code(&root, &promo_leaf(&Pubkey::new_unique(), "WELCOME"), &[leaves[1]]));
+        assert!(!verify_promo_code(&root, &promo_leaf(&redeemers[1], "WELCOME"), &[leaves[1]]));
+    }
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::hash::{hash_creators, hash_metadata};
+use mpl_bubblegum::types::MetadataArgs;
+use crate::global_accounts::lottery_account::{LotteryStateData, TreeRegistration};
+use crate::errors::errors::MyError;
+
+/// `(max_depth, max_buffer_size)` pairs supported by SPL Account Compression
+pub const SUPPORTED_TREE_DIMENSIONS: [(u32, u32); 34] = [
+    (3, 8),
+    (5, 8),
+    (6, 16),
+    (7, 16),
+    (8, 16),
+    (9, 16),
+    (10, 32),
+    (11, 32),
+    (12, 32),
+    (13, 32),
+    (14, 64),
+    (14, 256),
+    (14, 1024),
+    (14, 2048),
+    (15, 64),
+    (16, 64),
+    (17, 64),
+    (18, 64),
+    (19, 64),
+    (20, 64),
+    (20, 256),
+    (20, 1024),
+    (20, 2048),
+    (24, 64),
+    (24, 256),
+    (24, 512),
+    (24, 1024),
+    (24, 2048),
+    (26, 512),
+    (26, 1024),
+    (26, 2048),
+    (30, 512),
+    (30, 1024),
+    (30, 2048),
+];
+
+// Size of `ConcurrentMerkleTreeHeader` (V1)
+const TREE_HEADER_SIZE: usize = 2 + 54;
+
+/// Whether SPL Account Compression supports a tree with the given dimensions
+pub fn is_supported_tree(max_depth: u32, max_buffer_size: u32) -> bool {
+    SUPPORTED_TREE_DIMENSIONS.contains(&(max_depth, max_buffer_size))
+}
+
+/// Size in bytes of a concurrent Merkle tree account with the given dimensions
+///
+/// Mirrors `size_of::<ConcurrentMerkleTree<D, B>>()`: the sequence number, active
+/// index and buffer size counters, `B` change logs and the rightmost proof, where
+/// change logs and proofs both take `32 * D + 40` bytes. The canopy caches the top
+/// `canopy_depth` levels of the tree below the root, `2^(canopy_depth + 1) - 2` nodes.
+pub fn merkle_tree_account_size(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> usize {
+    let path_size = 32 * max_depth as usize + 40;
+    let canopy_size = 32 * ((1usize << (canopy_depth + 1)) - 2);
+    TREE_HEADER_SIZE + 24 + max_buffer_size as usize * path_size + path_size + canopy_size
+}
+
+/// Number of leaves Bubblegum has minted into `tree`, read from its tree config
+///
+/// This also counts leaves minted directly through Bubblegum by a tree delegate.
+pub fn tree_num_minted(tree: &TreeRegistration, tree_config: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(tree_config.key(), TreeConfig::find_pda(&tree.merkle_tree).0, MyError::InvalidTreeConfig);
+    Ok(TreeConfig::try_from(tree_config)?.num_minted)
+}
+
+/// Makes `merkle_tree` the lottery's current tree if tickets can be minted into it
+///
+/// The tree must be registered at or after the current tree and have room left. Rolling
+/// over past the current tree requires the tree configs of the trees skipped over, in
+/// order, each showing the tree is full. When minting into the tree is delegated, the
+/// delegate must be the signing `tree_delegate`; otherwise no delegate may be passed.
+pub fn select_tree(
+    lottery: &mut LotteryStateData,
+    merkle_tree: &Pubkey,
+    tree_config: &AccountInfo,
+    full_tree_configs: &[AccountInfo],
+    tree_delegate: Option<Pubkey>,
+) -> Result<()> {
+    let current = lottery.current_tree as usize;
+    let index = lottery.trees.iter()
+        .position(|tree| tree.merkle_tree == *merkle_tree)
+        .filter(|index| *index >= current)
+        .ok_or(MyError::InvalidMerkleTree)?;
+
+    let skipped = &lottery.trees[current..index];
+    require_eq!(full_tree_configs.len(), skipped.len(), MyError::InvalidTreeConfig);
+    for (tree, full_tree_config) in skipped.iter().zip(full_tree_configs) {
+        require!(tree_num_minted(tree, full_tree_config)? >= tree.capacity(), MyError::TreeNotFull);
+    }
+
+    let tree = &lottery.trees[index];
+    require!(tree_num_minted(tree, tree_config)? < tree.capacity(), MyError::NoTreeAvailable);
+    require!(tree.tree_delegate == tree_delegate, MyError::InvalidTreeDelegate);
+
+    lottery.current_tree = index as u8;
+    Ok(())
+}
+
+/// A ticket leaf rebuilt from its serial, with the Merkle proof Bubblegum needs to update it
+pub struct TicketLeaf<'a, 'info> {
+    pub metadata: MetadataArgs,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub proof: Vec<(&'a AccountInfo<'info>, bool, bool)>,
+}
+
+/// Rebuilds ticket `serial` of the lottery and takes its Merkle proof from `remaining_accounts`
+///
+/// The proof must cover exactly the levels of `merkle_tree` below its canopy and comes
+/// first, followed by `extra_accounts` accounts of the instruction, which are returned
+/// alongside the leaf.
+pub fn ticket_leaf<'a, 'info>(
+    lottery: &LotteryStateData,
+    merkle_tree: &Pubkey,
+    serial: u64,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    extra_accounts: usize,
+) -> Result<(TicketLeaf<'a, 'info>, &'a [AccountInfo<'info>])> {
+    let tree = lottery.find_tree(merkle_tree).ok_or(MyError::InvalidMerkleTree)?;
+    require_eq!(remaining_accounts.len(), tree.proof_length() + extra_accounts, MyError::InvalidProofLength);
+    let (proof_accounts, extra) = remaining_accounts.split_at(tree.proof_length());
+
+    let metadata = lottery.ticket_metadata(serial);
+    let data_hash = hash_metadata(&metadata)?;
+    let creator_hash = hash_creators(&metadata.creators);
+    let proof = proof_accounts
+        .iter()
+        .map(|account| (account, account.is_signer, account.is_writable))
+        .collect();
+
+    Ok((TicketLeaf { metadata, data_hash, creator_hash, proof }, extra))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Account sizes without canopy, as returned by `getConcurrentMerkleTreeAccountSize`
+    const ACCOUNT_SIZES: [(u32, u32, usize); 34] = [
+        (3, 8, 1_304),
+        (5, 8, 1_880),
+        (6, 16, 4_024),
+        (7, 16, 4_568),
+        (8, 16, 5_112),
+        (9, 16, 5_656),
+        (10, 32, 11_960),
+        (11, 32, 13_016),
+        (12, 32, 14_072),
+        (13, 32, 15_128),
+        (14, 64, 31_800),
+        (14, 256, 125_496),
+        (14, 1024, 500_280),
+        (14, 2048, 999_992),
+        (15, 64, 33_880),
+        (16, 64, 35_960),
+        (17, 64, 38_040),
+        (18, 64, 40_120),
+        (19, 64, 42_200),
+        (20, 64, 44_280),
+        (20, 256, 174_840),
+        (20, 1024, 697_080),
+        (20, 2048, 1_393_400),
+        (24, 64, 52_600),
+        (24, 256, 207_736),
+        (24, 512, 414_584),
+        (24, 1024, 828_280),
+        (24, 2048, 1_655_672),
+        (26, 512, 447_416),
+        (26, 1024, 893_880),
+        (26, 2048, 1_786_808),
+        (30, 512, 513_080),
+        (30, 1024, 1_025_080),
+        (30, 2048, 2_049_080),
+    ];
+
+    #[test]
+    fn account_size_of_every_supported_tree() {
+        for (max_depth, max_buffer_size, size) in ACCOUNT_SIZES {
+            assert!(is_supported_tree(max_depth, max_buffer_size));
+            assert_eq!(merkle_tree_account_size(max_depth, max_buffer_size, 0), size, "({max_depth}, {max_buffer_size})");
+        }
+        assert_eq!(ACCOUNT_SIZES.len(), SUPPORTED_TREE_DIMENSIONS.len());
+    }
+
+    #[test]
+    fn account_size_includes_canopy() {
+        // 2^12 - 2 cached nodes for a canopy of 11 levels
+        assert_eq!(merkle_tree_account_size(14, 64, 11), 31_800 + 32 * 4_094);
+    }
+
+    #[test]
+    fn unsupported_dimensions_are_rejected() {
+        assert!(!is_supported_tree(4, 8));
+        assert!(!is_supported_tree(14, 128));
+        assert!(!is_supported_tree(31, 2048));
+    }
+}
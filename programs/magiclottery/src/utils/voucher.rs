@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::errors::errors::MyError;
+
+/// Purchase voucher issued off-chain by the backend and signed with the authorizer key.
+///
+/// The signed message is the Borsh encoding of this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PurchaseVoucher {
+    pub buyer: Pubkey,
+    pub lottery: Pubkey,
+    pub quantity: u32,
+    pub price: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+// Layout of the Ed25519 program instruction data (see `solana_sdk::ed25519_instruction`)
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+/// Verifies that the instruction preceding the current one is an Ed25519 program
+/// instruction checking `signer`'s signature over the given voucher.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar` - The instructions sysvar account
+/// * `signer` - The public key expected to have signed the voucher
+/// * `voucher` - The voucher whose Borsh encoding must be the signed message
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns Ok(()) if the signature was verified, otherwise returns an error
+pub fn verify_voucher_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    voucher: &PurchaseVoucher,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, MyError::MissingVoucherSignature);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, MyError::MissingVoucherSignature);
+    require!(ed25519_ix.accounts.is_empty(), MyError::InvalidVoucherSignature);
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE && data[0] == 1,
+        MyError::InvalidVoucherSignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix_index = read_u16(offsets + 2);
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let public_key_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // All signature data must live in the Ed25519 instruction itself
+    require!(
+        signature_ix_index == u16::MAX
+            && public_key_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        MyError::InvalidVoucherSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_SIZE)
+        .ok_or(MyError::InvalidVoucherSignature)?;
+    require!(public_key == signer.as_ref(), MyError::InvalidVoucherSignature);
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(MyError::InvalidVoucherSignature)?;
+    require!(message == voucher.try_to_vec()?.as_slice(), MyError::InvalidVoucherSignature);
+
+    Ok(())
+}
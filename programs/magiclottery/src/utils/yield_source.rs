@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::errors::errors::MyError;
+
+// Anchor instruction discriminators of the yield source interface, `sha256("global:<name>")[..8]`
+const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+/// Accounts of a CPI into a yield source program
+///
+/// Any program implementing the interface can back a no-loss lottery:
+///
+/// * `open_position()` with accounts `[position (w), owner (s, w), payer (s, w), system_program]`
+/// * `deposit(amount: u64)` moving lamports from the owner, with `[position (w), owner (s, w), system_program]`
+/// * `withdraw(amount: u64)` moving lamports to the owner, with `[position (w), owner (s, w)]`
+///
+/// The position account is owned by the yield program and its data starts with an
+/// 8 byte discriminator, the position owner and the `u64` lamport value it can withdraw.
+pub struct YieldSource<'a, 'info> {
+    pub yield_program: &'a AccountInfo<'info>,
+    pub position: &'a AccountInfo<'info>,
+    pub owner: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> YieldSource<'a, 'info> {
+    /// Opens the position of the owner, its rent paid by `payer`
+    pub fn open_position(&self, payer: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.yield_program.key(),
+            accounts: vec![
+                AccountMeta::new(self.position.key(), false),
+                AccountMeta::new(self.owner.key(), true),
+                AccountMeta::new(payer.key(), true),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data: OPEN_POSITION_DISCRIMINATOR.to_vec(),
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.position.clone(),
+                self.owner.clone(),
+                payer.clone(),
+                self.system_program.clone(),
+                self.yield_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    /// Deposits `amount` lamports of the owner into the position
+    pub fn deposit(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.yield_program.key(),
+            accounts: vec![
+                AccountMeta::new(self.position.key(), false),
+                AccountMeta::new(self.owner.key(), true),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data: [DEPOSIT_DISCRIMINATOR.as_slice(), &amount.to_le_bytes()].concat(),
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.position.clone(),
+                self.owner.clone(),
+                self.system_program.clone(),
+                self.yield_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    /// Withdraws `amount` lamports from the position back to the owner
+    pub fn withdraw(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.yield_program.key(),
+            accounts: vec![
+                AccountMeta::new(self.position.key(), false),
+                AccountMeta::new(self.owner.key(), true),
+            ],
+            data: [WITHDRAW_DISCRIMINATOR.as_slice(), &amount.to_le_bytes()].concat(),
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.position.clone(),
+                self.owner.clone(),
+                self.yield_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    /// Lamports the owner can currently withdraw from the position
+    pub fn position_value(&self) -> Result<u64> {
+        require_keys_eq!(*self.position.owner, self.yield_program.key(), MyError::InvalidYieldPosition);
+
+        let data = self.position.try_borrow_data()?;
+        require!(data.len() >= 8 + 32 + 8, MyError::InvalidYieldPosition);
+        require!(data[8..40] == self.owner.key().to_bytes(), MyError::InvalidYieldPosition);
+
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&data[40..48]);
+        Ok(u64::from_le_bytes(value))
+    }
+}
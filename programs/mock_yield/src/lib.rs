@@ -0,0 +1,153 @@
+//! Mock Yield Program: A yield source for testing no-loss lotteries.
+//!
+//! Implements the yield source interface expected by Magic Lottery: positions are
+//! opened, funded and drained by their owner, and the position value is stored right
+//! after the owner. Instead of earning anything, yield is simulated with `accrue`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+
+pub const POSITION_SEED: &[u8] = b"position";
+declare_id!("6BUrAZrRw9ESRt8PMNp7wKS7RWYsGTjPZoqv3CWk8DY7");
+
+/// Main program module for the mock yield source
+#[program]
+pub mod mock_yield {
+    use super::*;
+
+    /// Open a position for the owner
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.value = 0;
+        Ok(())
+    }
+
+    /// Deposit lamports from the owner into the position
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.position.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.value = position.value.checked_add(amount).ok_or(MockYieldError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Withdraw lamports from the position back to the owner
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        require!(amount <= position.value, MockYieldError::InsufficientValue);
+        position.value -= amount;
+
+        // The position is owned by this program, so its lamports can be moved directly
+        position.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+        Ok(())
+    }
+
+    /// Simulate yield by adding lamports from any funder to the position value
+    pub fn accrue(ctx: Context<Accrue>, amount: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.position.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.value = position.value.checked_add(amount).ok_or(MockYieldError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + YieldPosition::MAX_SIZE,
+        seeds = [POSITION_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, YieldPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [POSITION_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, YieldPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [POSITION_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, YieldPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Accrue<'info> {
+    #[account(mut)]
+    pub position: Account<'info, YieldPosition>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// A position in the yield source, worth `value` lamports to its owner
+#[account]
+pub struct YieldPosition {
+    pub owner: Pubkey,
+    pub value: u64,
+}
+
+impl YieldPosition {
+    pub const MAX_SIZE: usize = 32 + 8;
+}
+
+#[error_code]
+pub enum MockYieldError {
+    #[msg("Withdrawal exceeds the position value")]
+    InsufficientValue,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,544 @@
+import { BN, Program, utils } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+} from "@solana/web3.js";
+import {
+  ConcurrentMerkleTreeAccount,
+  getConcurrentMerkleTreeAccountSize,
+  MerkleTree,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+} from "@solana/spl-account-compression";
+import { BankrunProvider } from "anchor-bankrun";
+import { AddedAccount, Clock, ProgramTestContext, start } from "solana-bankrun";
+import { keccak_256 } from "js-sha3";
+import { expect } from "chai";
+import * as fs from "fs";
+import { MagicLottery } from "../target/types/magic_lottery";
+import { MockYield } from "../target/types/mock_yield";
+import magicLotteryIdl from "../target/idl/magic_lottery.json";
+import mockYieldIdl from "../target/idl/mock_yield.json";
+
+// The Metaplex programs are dumped from mainnet into tests/fixtures by `yarn fixtures`
+const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const BPF_LOADER_ID = new PublicKey("BPFLoader2111111111111111111111111111111111");
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+const SYSVAR_ID = new PublicKey("Sysvar1111111111111111111111111111111111111");
+
+const MAX_DEPTH = 3;
+const MAX_BUFFER_SIZE = 8;
+const PRICE = LAMPORTS_PER_SOL;
+const DEPOSIT = 1.5 * LAMPORTS_PER_SOL;
+const YIELD = 0.25 * LAMPORTS_PER_SOL;
+
+const u64 = (value: number | bigint) => {
+  const bytes = Buffer.alloc(8);
+  bytes.writeBigUInt64LE(BigInt(value));
+  return bytes;
+};
+
+const keccak = (...parts: Buffer[]) => Buffer.from(keccak_256.arrayBuffer(Buffer.concat(parts)));
+
+const fundedWallet = (wallet: Keypair): AddedAccount => ({
+  address: wallet.publicKey,
+  info: {
+    lamports: 100 * LAMPORTS_PER_SOL,
+    data: Buffer.alloc(0),
+    owner: SystemProgram.programId,
+    executable: false,
+  },
+});
+
+const programAccount = (programId: PublicKey, path: string): AddedAccount => ({
+  address: programId,
+  info: {
+    lamports: LAMPORTS_PER_SOL,
+    data: fs.readFileSync(path),
+    owner: BPF_LOADER_ID,
+    executable: true,
+  },
+});
+
+// `initialize_central_authority` checks the upgrade authority recorded in the program data account
+const upgradeableProgramAccounts = (
+  programId: PublicKey,
+  path: string,
+  upgradeAuthority: PublicKey
+): AddedAccount[] => {
+  const [programData] = PublicKey.findProgramAddressSync([programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID);
+
+  const program = Buffer.alloc(36);
+  program.writeUInt32LE(2, 0);
+  programData.toBuffer().copy(program, 4);
+
+  // State tag, deployment slot and optional upgrade authority, followed by the ELF
+  const header = Buffer.alloc(45);
+  header.writeUInt32LE(3, 0);
+  header.writeUInt8(1, 12);
+  upgradeAuthority.toBuffer().copy(header, 13);
+
+  return [
+    {
+      address: programId,
+      info: { lamports: LAMPORTS_PER_SOL, data: program, owner: BPF_LOADER_UPGRADEABLE_ID, executable: true },
+    },
+    {
+      address: programData,
+      info: {
+        lamports: 100 * LAMPORTS_PER_SOL,
+        data: Buffer.concat([header, fs.readFileSync(path)]),
+        owner: BPF_LOADER_UPGRADEABLE_ID,
+        executable: false,
+      },
+    },
+  ];
+};
+
+// SlotHashes entries, most recent first, as read by `utils::draw::slot_hash_at`
+const slotHashesData = (entries: [bigint, Buffer][]) => {
+  const data = Buffer.alloc(8 + 40 * entries.length);
+  data.writeBigUInt64LE(BigInt(entries.length), 0);
+  entries.forEach(([slot, hash], i) => {
+    data.writeBigUInt64LE(slot, 8 + 40 * i);
+    hash.copy(data, 16 + 40 * i);
+  });
+  return data;
+};
+
+// Mirrors `utils::draw::draw_winning_ticket`
+const drawWinningTicket = (seed: Buffer, attempt: number, ticketsMinted: number) =>
+  Number(1n + (keccak(seed, u64(attempt)).readBigUInt64LE(0) % BigInt(ticketsMinted)));
+
+/// Off-chain copy of the ticket tree leaves, used to build Merkle proofs
+class TicketTree {
+  leaves: Buffer[];
+
+  constructor(public address: PublicKey, maxDepth: number) {
+    this.leaves = Array.from({ length: 2 ** maxDepth }, () => Buffer.alloc(32));
+  }
+
+  /// Records the leaf written by the latest change to the tree
+  async sync(context: ProgramTestContext) {
+    const account = await context.banksClient.getAccount(this.address);
+    const tree = ConcurrentMerkleTreeAccount.fromBuffer(Buffer.from(account!.data));
+    const changeLog = tree.tree.changeLogs[new BN(tree.tree.activeIndex).toNumber()];
+    this.leaves[changeLog.index] = changeLog.pathNodes[0].toBuffer();
+    return tree.getCurrentRoot();
+  }
+
+  proof(index: number): { root: number[]; proof: AccountMeta[] } {
+    const { root, proof } = new MerkleTree(this.leaves).getProof(index);
+    return {
+      root: Array.from(root),
+      proof: proof.map((node) => ({ pubkey: new PublicKey(node), isSigner: false, isWritable: false })),
+    };
+  }
+}
+
+describe("no-loss lottery", () => {
+  const upgradeAuthority = Keypair.generate();
+  const authorizer = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const collectionMint = Keypair.generate();
+  const merkleTree = Keypair.generate();
+  const treasury = Keypair.generate();
+  const secret = Buffer.alloc(32, 7);
+
+  const programId = new PublicKey(magicLotteryIdl.address);
+  const mockYieldId = new PublicKey(mockYieldIdl.address);
+  const pda = (seeds: Buffer[], owner = programId) => PublicKey.findProgramAddressSync(seeds, owner)[0];
+
+  const centralAuthority = pda([Buffer.from("B_central_authority")]);
+  const lottery = pda([Buffer.from("B_lottery"), collectionMint.publicKey.toBuffer()]);
+  const vault = pda([Buffer.from("B_vault"), lottery.toBuffer()]);
+  const yieldVault = pda([Buffer.from("B_yield_vault"), lottery.toBuffer()]);
+  const ticketLock = pda([Buffer.from("B_ticket_lock"), lottery.toBuffer()]);
+  const ticketRecord = (serial: number) => pda([Buffer.from("B_ticket"), lottery.toBuffer(), u64(serial)]);
+  const position = pda([Buffer.from("position"), vault.toBuffer()], mockYieldId);
+  const programData = pda([programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID);
+  const treeConfig = pda([merkleTree.publicKey.toBuffer()], BUBBLEGUM_PROGRAM_ID);
+  const bubblegumSigner = pda([Buffer.from("collection_cpi")], BUBBLEGUM_PROGRAM_ID);
+  const collectionMetadata = pda(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), collectionMint.publicKey.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const editionAccount = pda(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      collectionMint.publicKey.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+
+  const tree = new TicketTree(merkleTree.publicKey, MAX_DEPTH);
+  const computeBudget = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_000_000 });
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<MagicLottery>;
+  let mockYield: Program<MockYield>;
+  let winner: Keypair;
+  let loser: Keypair;
+
+  const bubblegumAccounts = () => ({
+    centralAuthority,
+    lottery,
+    merkleTree: merkleTree.publicKey,
+    treeConfig,
+    collectionMint: collectionMint.publicKey,
+    collectionMetadata,
+    editionAccount,
+    bubblegumSigner,
+    logWrapper: SPL_NOOP_PROGRAM_ID,
+    compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+    tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+  });
+
+  const yieldAccounts = () => ({
+    authorizerWallet: authorizer.publicKey,
+    centralAuthority,
+    lottery,
+    vault,
+    yieldVault,
+    yieldProgram: mockYieldId,
+    position,
+    systemProgram: SystemProgram.programId,
+  });
+
+  const lotteryState = () => program.account.lotteryStateData.fetch(lottery);
+  const yieldVaultState = () => program.account.yieldVault.fetch(yieldVault);
+  const vaultBalance = () => context.banksClient.getBalance(vault);
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (error) {
+      expect(String(error)).to.include(code);
+      return;
+    }
+    expect.fail(`expected ${code}`);
+  };
+
+  const buyTicket = (buyer: Keypair, amount: number, serial: number) =>
+    program.methods
+      .buyTicket(new BN(amount), null, null, Buffer.from([]), new BN(amount))
+      .accountsPartial({
+        ...bubblegumAccounts(),
+        payer: buyer.publicKey,
+        authorizerWallet: null,
+        ticketPicks: null,
+        ticketRecord: ticketRecord(serial),
+        voucherRecord: null,
+        buyerRecord: null,
+        referrer: null,
+        referrerBalance: null,
+        yieldVault,
+        holderTokenAccount: null,
+        holderMetadata: null,
+        instructionsSysvar: null,
+        leafOwner: buyer.publicKey,
+        ticketLock,
+        treeDelegate: null,
+        paymentReceiver: vault,
+      })
+      .preInstructions([computeBudget])
+      .signers([buyer])
+      .rpc();
+
+  before(async () => {
+    context = await start(
+      [],
+      [
+        ...upgradeableProgramAccounts(programId, "target/deploy/magic_lottery.so", upgradeAuthority.publicKey),
+        programAccount(mockYieldId, "target/deploy/mock_yield.so"),
+        programAccount(BUBBLEGUM_PROGRAM_ID, "tests/fixtures/mpl_bubblegum.so"),
+        programAccount(TOKEN_METADATA_PROGRAM_ID, "tests/fixtures/mpl_token_metadata.so"),
+        programAccount(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, "tests/fixtures/spl_account_compression.so"),
+        programAccount(SPL_NOOP_PROGRAM_ID, "tests/fixtures/spl_noop.so"),
+        ...[upgradeAuthority, authorizer, alice, bob].map(fundedWallet),
+      ]
+    );
+    provider = new BankrunProvider(context);
+    program = new Program<MagicLottery>(magicLotteryIdl as MagicLottery, provider);
+    mockYield = new Program<MockYield>(mockYieldIdl as MockYield, provider);
+
+    await program.methods
+      .initializeCentralAuthority(authorizer.publicKey)
+      .accountsPartial({
+        payer: provider.publicKey,
+        centralAuthority,
+        systemProgram: SystemProgram.programId,
+        upgradeAuthority: upgradeAuthority.publicKey,
+        programData,
+      })
+      .signers([upgradeAuthority])
+      .rpc();
+
+    await program.methods
+      .createLottery(
+        "Magic Lottery",
+        "MAGIC",
+        "https://example.com/collection.json",
+        "https://example.com/tickets",
+        false,
+        null,
+        { sellerFeeBasisPoints: 0, creators: [] }
+      )
+      .accountsPartial({
+        payer: provider.publicKey,
+        centralAuthority,
+        authorizerWallet: authorizer.publicKey,
+        collectionMint: collectionMint.publicKey,
+        lottery,
+        associatedTokenAccount: utils.token.associatedAddress({
+          mint: collectionMint.publicKey,
+          owner: centralAuthority,
+        }),
+        metadataAccount: collectionMetadata,
+        masterEditionAccount: editionAccount,
+        tokenProgram: utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: utils.token.ASSOCIATED_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .preInstructions([computeBudget])
+      .signers([authorizer, collectionMint])
+      .rpc();
+
+    const space = getConcurrentMerkleTreeAccountSize(MAX_DEPTH, MAX_BUFFER_SIZE, 0);
+    const rent = await context.banksClient.getRent();
+    await program.methods
+      .createTree(MAX_DEPTH, MAX_BUFFER_SIZE, 0)
+      .accountsPartial({
+        payer: provider.publicKey,
+        authorizerWallet: authorizer.publicKey,
+        centralAuthority,
+        lottery,
+        merkleTree: merkleTree.publicKey,
+        treeConfig,
+        bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        logWrapper: SPL_NOOP_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      })
+      .preInstructions([
+        SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: merkleTree.publicKey,
+          lamports: Number(rent.minimumBalance(BigInt(space))),
+          space,
+          programId: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        }),
+      ])
+      .signers([authorizer, merkleTree])
+      .rpc();
+  });
+
+  it("enables no-loss mode with a yield position owned by the vault", async () => {
+    await program.methods
+      .enableNoLoss()
+      .accountsPartial({ ...yieldAccounts(), payer: provider.publicKey })
+      .signers([authorizer])
+      .rpc();
+
+    expect((await lotteryState()).noLoss).to.be.true;
+    const yieldVaultAccount = await yieldVaultState();
+    expect(yieldVaultAccount.position.toBase58()).to.equal(position.toBase58());
+    expect(yieldVaultAccount.principal.toNumber()).to.equal(0);
+  });
+
+  it("rejects free entries", async () => {
+    await expectError(buyTicket(alice, 0, 1), "ZeroDeposit");
+  });
+
+  it("adds ticket payments to the principal", async () => {
+    await buyTicket(alice, PRICE, 1);
+    await tree.sync(context);
+    await buyTicket(bob, PRICE, 2);
+    await tree.sync(context);
+
+    expect((await yieldVaultState()).principal.toNumber()).to.equal(2 * PRICE);
+    expect((await lotteryState()).ticketsMinted.toNumber()).to.equal(2);
+  });
+
+  it("deposits principal and harvests the yield into the vault", async () => {
+    await program.methods.depositToYield(new BN(DEPOSIT)).accountsPartial(yieldAccounts()).signers([authorizer]).rpc();
+    expect((await yieldVaultState()).deposited.toNumber()).to.equal(DEPOSIT);
+
+    await mockYield.methods
+      .accrue(new BN(YIELD))
+      .accountsPartial({ position, funder: provider.publicKey, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    const vaultBefore = await vaultBalance();
+    await program.methods.harvestYield().accountsPartial(yieldAccounts()).signers([authorizer]).rpc();
+    expect(Number((await vaultBalance()) - vaultBefore)).to.equal(YIELD);
+    expect((await yieldVaultState()).harvestedYield.toNumber()).to.equal(YIELD);
+
+    // Refunds are paid out of the vault, so the principal comes back before the draw
+    await program.methods.withdrawFromYield(new BN(DEPOSIT)).accountsPartial(yieldAccounts()).signers([authorizer]).rpc();
+    expect((await yieldVaultState()).deposited.toNumber()).to.equal(0);
+  });
+
+  it("draws a live ticket from the hash of the committed draw slot", async () => {
+    await program.methods
+      .commitDraw(Array.from(keccak(secret)))
+      .accountsPartial({ authorizerWallet: authorizer.publicKey, centralAuthority, lottery })
+      .signers([authorizer])
+      .rpc();
+    const drawSlot = BigInt((await lotteryState()).drawSlot.toString());
+
+    context.warpToSlot(drawSlot + 1n);
+    const slotHash = Buffer.alloc(32, 42);
+    context.setAccount(SYSVAR_SLOT_HASHES_PUBKEY, {
+      lamports: LAMPORTS_PER_SOL,
+      data: slotHashesData([
+        [drawSlot, slotHash],
+        [drawSlot - 1n, Buffer.alloc(32, 1)],
+      ]),
+      owner: SYSVAR_ID,
+      executable: false,
+    });
+
+    const seed = keccak(secret, slotHash, lottery.toBuffer());
+    const serial = drawWinningTicket(seed, 0, 2);
+    [winner, loser] = serial === 1 ? [alice, bob] : [bob, alice];
+
+    await program.methods
+      .revealDraw(Array.from(secret))
+      .accountsPartial({
+        authorizerWallet: authorizer.publicKey,
+        centralAuthority,
+        lottery,
+        vault,
+        yieldVault,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .remainingAccounts([{ pubkey: ticketRecord(serial), isSigner: false, isWritable: false }])
+      .signers([authorizer])
+      .rpc();
+
+    // Only the harvested yield is up for grabs, the principal stays refundable
+    const state = await lotteryState();
+    const rent = await context.banksClient.getRent();
+    expect(state.status).to.deep.equal({ drawn: {} });
+    expect(state.winningTicket.toNumber()).to.equal(serial);
+    expect(state.prizePool.toNumber()).to.equal(
+      Number((await vaultBalance()) - rent.minimumBalance(0n)) - 2 * PRICE
+    );
+  });
+
+  it("pays the prize and refunds the principal of the winning ticket", async () => {
+    const serial = (await lotteryState()).winningTicket.toNumber();
+    const prizePool = (await lotteryState()).prizePool.toNumber();
+    const { root, proof } = tree.proof(serial - 1);
+
+    const vaultBefore = await vaultBalance();
+    await program.methods
+      .claimPrize(new BN(serial), root, new BN(serial - 1), serial - 1)
+      .accountsPartial({
+        ...bubblegumAccounts(),
+        vault,
+        yieldVault,
+        ticketPicks: null,
+        ticketRecord: ticketRecord(serial),
+        ticketPayer: winner.publicKey,
+        leafOwner: winner.publicKey,
+        leafDelegate: ticketLock,
+      })
+      .remainingAccounts(proof)
+      .preInstructions([computeBudget])
+      .signers([winner])
+      .rpc();
+    await tree.sync(context);
+
+    expect(Number(vaultBefore - (await vaultBalance()))).to.equal(prizePool + PRICE);
+    expect((await yieldVaultState()).principal.toNumber()).to.equal(PRICE);
+    expect(await context.banksClient.getAccount(ticketRecord(serial))).to.be.null;
+  });
+
+  it("settles once the claim period is over", async () => {
+    const settle = (treasury: PublicKey) =>
+      program.methods
+        .settleLottery()
+        .accountsPartial({
+          authorizerWallet: authorizer.publicKey,
+          centralAuthority,
+          lottery,
+          vault,
+          yieldVault,
+          treasury,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authorizer])
+        .rpc();
+
+    // Nothing is swept to the default address before the treasury is configured
+    await expectError(settle(PublicKey.default), "TreasuryNotSet");
+    await program.methods
+      .setProtocolFee(0, treasury.publicKey)
+      .accountsPartial({ authorizerWallet: authorizer.publicKey, centralAuthority })
+      .signers([authorizer])
+      .rpc();
+
+    await expectError(settle(treasury.publicKey), "ClaimPeriodNotOver");
+
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt((await lotteryState()).claimDeadline.toString())
+      )
+    );
+    await settle(treasury.publicKey);
+
+    expect((await lotteryState()).status).to.deep.equal({ settled: {} });
+    expect((await yieldVaultState()).principal.toNumber()).to.equal(PRICE);
+  });
+
+  it("refunds the principal of the losing ticket after settlement", async () => {
+    const serial = winner === alice ? 2 : 1;
+    const { root, proof } = tree.proof(serial - 1);
+
+    const vaultBefore = await vaultBalance();
+    await program.methods
+      .disolveTicket(new BN(serial), root, new BN(serial - 1), serial - 1)
+      .accountsPartial({
+        ...bubblegumAccounts(),
+        payer: loser.publicKey,
+        authorizerWallet: authorizer.publicKey,
+        vault,
+        yieldVault,
+        ticketRecord: ticketRecord(serial),
+        ticketPicks: null,
+        ticketPayer: loser.publicKey,
+        leafOwner: loser.publicKey,
+        leafDelegate: ticketLock,
+      })
+      .remainingAccounts(proof)
+      .preInstructions([computeBudget])
+      .signers([loser, authorizer])
+      .rpc();
+
+    expect(Number(vaultBefore - (await vaultBalance()))).to.equal(PRICE);
+    expect((await yieldVaultState()).principal.toNumber()).to.equal(0);
+    expect((await lotteryState()).collectionSize.toNumber()).to.equal(0);
+  });
+});